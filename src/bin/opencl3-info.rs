@@ -0,0 +1,381 @@
+// Copyright (c) 2026 Via Technology Ltd. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Displays all the information about the OpenCL platforms and devices on a
+//! system, as text or JSON, and compares saved JSON reports.
+//!
+//! Build and run with: `cargo run --features info --bin opencl3-info -- --help`
+
+use opencl3::device::{CL_DEVICE_TYPE_ALL, Device, DeviceInfo, device_type_text, vendor_id_text};
+use opencl3::platform::{PlatformInfo, get_platforms};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::process;
+
+const USAGE: &str = "Usage: opencl3-info [OPTIONS]
+       opencl3-info --diff <REPORT_A> <REPORT_B>
+
+Displays information about the OpenCL platforms and devices on this system.
+
+Options:
+  --json                 output a JSON report instead of text
+  --platform <FILTER>    only show platforms with this index or a name containing FILTER
+  --device <FILTER>      only show devices with this index or a name containing FILTER
+  --diff <A> <B>         compare two JSON reports, exits with 1 if they differ
+  -h, --help             display this message";
+
+/// The information about a platform and its devices.
+#[derive(Debug, Serialize, Deserialize)]
+struct PlatformReport {
+    index: usize,
+    info: PlatformInfo,
+    devices: Vec<DeviceReport>,
+}
+
+/// The information about a device.
+#[derive(Debug, Serialize, Deserialize)]
+struct DeviceReport {
+    index: usize,
+    info: DeviceInfo,
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    json: bool,
+    platform: Option<String>,
+    device: Option<String>,
+    diff: Option<(String, String)>,
+    help: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {name}"))
+        };
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--platform" => options.platform = Some(value("--platform")?),
+            "--device" => options.device = Some(value("--device")?),
+            "--diff" => {
+                let a = value("--diff")?;
+                let b = value("--diff")?;
+                options.diff = Some((a, b));
+            }
+            "-h" | "--help" => options.help = true,
+            _ => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+    Ok(options)
+}
+
+/// Whether an index and name match a filter: either the index or part of the
+/// name, ignoring case.
+fn is_match(filter: Option<&String>, index: usize, name: Option<&String>) -> bool {
+    filter.is_none_or(|filter| {
+        filter.parse::<usize>().ok() == Some(index)
+            || name.is_some_and(|name| name.to_lowercase().contains(&filter.to_lowercase()))
+    })
+}
+
+fn collect_reports(options: &Options) -> Result<Vec<PlatformReport>, String> {
+    let mut reports = Vec::<PlatformReport>::new();
+
+    let platforms = get_platforms().map_err(|e| e.to_string())?;
+    for (index, platform) in platforms.iter().enumerate() {
+        let info = platform.info();
+        if !is_match(options.platform.as_ref(), index, info.name.as_ref()) {
+            continue;
+        }
+
+        // A platform without any devices returns CL_DEVICE_NOT_FOUND
+        let device_ids = platform.get_devices(CL_DEVICE_TYPE_ALL).unwrap_or_default();
+        let devices = device_ids
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| DeviceReport {
+                index,
+                info: Device::new(id).info(),
+            })
+            .filter(|device| {
+                is_match(
+                    options.device.as_ref(),
+                    device.index,
+                    device.info.name.as_ref(),
+                )
+            })
+            .collect();
+
+        reports.push(PlatformReport {
+            index,
+            info,
+            devices,
+        });
+    }
+
+    Ok(reports)
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("unsupported"),
+        Value::String(text) => text.clone(),
+        _ => value.to_string(),
+    }
+}
+
+fn print_fields(indent: &str, value: &Value) {
+    if let Value::Object(fields) = value {
+        for (key, value) in fields {
+            let text = match (key.as_str(), value) {
+                ("dev_type", Value::Number(n)) => n.as_u64().map_or_else(
+                    || value_text(value),
+                    |n| format!("{n:X}, {}", device_type_text(n)),
+                ),
+                ("vendor_id", Value::Number(n)) => {
+                    n.as_u64().and_then(|n| u32::try_from(n).ok()).map_or_else(
+                        || value_text(value),
+                        |n| format!("{n:X}, {}", vendor_id_text(n)),
+                    )
+                }
+                _ => value_text(value),
+            };
+            println!("{indent}{key}: {text}");
+        }
+    }
+}
+
+fn print_text(reports: &[PlatformReport]) -> Result<(), String> {
+    println!("Number of platforms: {}", reports.len());
+    for platform in reports {
+        println!();
+        println!(
+            "Platform #{}: {}",
+            platform.index,
+            platform.info.name.as_deref().unwrap_or_default()
+        );
+        print_fields(
+            "  ",
+            &serde_json::to_value(&platform.info).map_err(|e| e.to_string())?,
+        );
+
+        println!("  Number of devices: {}", platform.devices.len());
+        for device in &platform.devices {
+            println!();
+            println!(
+                "  Device #{}: {}",
+                device.index,
+                device.info.name.as_deref().unwrap_or_default()
+            );
+            print_fields(
+                "    ",
+                &serde_json::to_value(&device.info).map_err(|e| e.to_string())?,
+            );
+        }
+    }
+    Ok(())
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Print the differences between two JSON values.
+/// Arrays of objects, e.g. platforms and devices, are compared element by element,
+/// other arrays are compared as a whole.
+///
+/// returns the number of differences.
+fn diff_values(path: &str, a: &Value, b: &Value) -> usize {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut count = 0;
+            for (key, a_value) in a {
+                let key_path = join_path(path, key);
+                count += b.get(key).map_or_else(
+                    || {
+                        println!("- {key_path}: {}", value_text(a_value));
+                        1
+                    },
+                    |b_value| diff_values(&key_path, a_value, b_value),
+                );
+            }
+            for (key, b_value) in b {
+                if !a.contains_key(key) {
+                    println!("+ {}: {}", join_path(path, key), value_text(b_value));
+                    count += 1;
+                }
+            }
+            count
+        }
+        (Value::Array(a), Value::Array(b)) if a.iter().chain(b.iter()).all(Value::is_object) => {
+            let mut count = 0;
+            for index in 0..a.len().max(b.len()) {
+                let index_path = format!("{path}[{index}]");
+                match (a.get(index), b.get(index)) {
+                    (Some(a_value), Some(b_value)) => {
+                        count += diff_values(&index_path, a_value, b_value);
+                    }
+                    (Some(_), None) => {
+                        println!("- {index_path}");
+                        count += 1;
+                    }
+                    (None, Some(_)) => {
+                        println!("+ {index_path}");
+                        count += 1;
+                    }
+                    (None, None) => {}
+                }
+            }
+            count
+        }
+        _ if a == b => 0,
+        _ => {
+            println!("{path}: {} -> {}", value_text(a), value_text(b));
+            1
+        }
+    }
+}
+
+fn read_report(path: &str) -> Result<Value, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    // Check that the file is a report before comparing it
+    let reports: Vec<PlatformReport> =
+        serde_json::from_str(&text).map_err(|e| format!("{path}: {e}"))?;
+    serde_json::to_value(reports).map_err(|e| format!("{path}: {e}"))
+}
+
+/// returns the process exit code.
+fn run() -> Result<i32, String> {
+    let options = parse_args(env::args().skip(1))?;
+    if options.help {
+        println!("{USAGE}");
+        return Ok(0);
+    }
+
+    if let Some((a, b)) = &options.diff {
+        let count = diff_values("platforms", &read_report(a)?, &read_report(b)?);
+        println!("{count} difference(s)");
+        return Ok(i32::from(0 < count));
+    }
+
+    let reports = collect_reports(&options)?;
+    if options.json {
+        let json = serde_json::to_string_pretty(&reports).map_err(|e| e.to_string())?;
+        println!("{json}");
+    } else {
+        print_text(&reports)?;
+    }
+    Ok(0)
+}
+
+fn main() {
+    match run() {
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("opencl3-info: {e}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| (*arg).to_owned())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args(&[])).expect("parse_args failed");
+        assert!(!options.json && !options.help);
+        assert!(options.platform.is_none() && options.device.is_none());
+
+        let options = parse_args(args(&["--json", "--platform", "0", "--device", "gpu"]))
+            .expect("parse_args failed");
+        assert!(options.json);
+        assert_eq!(Some("0".to_owned()), options.platform);
+        assert_eq!(Some("gpu".to_owned()), options.device);
+
+        let options =
+            parse_args(args(&["--diff", "a.json", "b.json", "-h"])).expect("parse_args failed");
+        assert_eq!(
+            Some(("a.json".to_owned(), "b.json".to_owned())),
+            options.diff
+        );
+        assert!(options.help);
+
+        assert_eq!(
+            Err("missing value for --diff".to_owned()),
+            parse_args(args(&["--diff", "a.json"])).map(|_| ())
+        );
+        assert_eq!(
+            Err("missing value for --platform".to_owned()),
+            parse_args(args(&["--platform"])).map(|_| ())
+        );
+        assert_eq!(
+            Err("unknown argument: --xml".to_owned()),
+            parse_args(args(&["--xml"])).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_is_match() {
+        let name = "NVIDIA GeForce RTX".to_owned();
+        assert!(is_match(None, 0, Some(&name)));
+        assert!(is_match(Some(&"1".to_owned()), 1, Some(&name)));
+        assert!(!is_match(Some(&"1".to_owned()), 0, Some(&name)));
+        assert!(is_match(Some(&"geforce".to_owned()), 0, Some(&name)));
+        assert!(!is_match(Some(&"radeon".to_owned()), 0, Some(&name)));
+        assert!(!is_match(Some(&"geforce".to_owned()), 0, None));
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let a = json!([{
+            "index": 0,
+            "info": {"name": "platform", "extensions": ["a", "b"]},
+            "devices": [{"index": 0}, {"index": 1}]
+        }]);
+        assert_eq!(0, diff_values("platforms", &a, &a));
+
+        // A changed value, a changed array of values and a removed device
+        let b = json!([{
+            "index": 0,
+            "info": {"name": "other", "extensions": ["a"]},
+            "devices": [{"index": 0}]
+        }]);
+        assert_eq!(3, diff_values("platforms", &a, &b));
+
+        // An added and a removed field
+        let c = json!({"a": 1});
+        let d = json!({"b": 1});
+        assert_eq!(2, diff_values("", &c, &d));
+        assert_eq!(1, diff_values("", &json!(null), &json!(1)));
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2026 Via Technology Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust closures as the user_data of OpenCL callback functions.
+//!
+//! A closure that OpenCL calls once, e.g. when an event's command completes,
+//! is boxed into the user_data of the callback function and freed after it
+//! has been called. Panics are caught, since they must not unwind into the
+//! OpenCL implementation.
+
+use super::Result;
+use libc::c_void;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A Rust closure that an OpenCL callback function calls once.
+type OnceCallback<A> = Box<dyn FnOnce(A) + Send>;
+
+/// Run the body of an OpenCL callback function, catching any panic since
+/// it must not unwind into the OpenCL implementation.
+pub fn catch_panic(f: impl FnOnce()) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(f));
+}
+
+/// Register a closure with an OpenCL function that takes a callback function
+/// and its user_data, freeing the closure if the registration fails.
+///
+/// * `callback` - the closure, called by [call_once] from the callback function.
+/// * `register` - calls the OpenCL function with the user_data of the closure.
+pub fn register_once<A, F>(
+    callback: F,
+    register: impl FnOnce(*mut c_void) -> Result<()>,
+) -> Result<()>
+where
+    F: FnOnce(A) + Send + 'static,
+    A: 'static,
+{
+    let callback: Box<OnceCallback<A>> = Box::new(Box::new(callback));
+    let user_data = Box::into_raw(callback).cast::<c_void>();
+    register(user_data).inspect_err(|_| {
+        drop(unsafe { Box::from_raw(user_data.cast::<OnceCallback<A>>()) });
+    })
+}
+
+/// Call and free a closure registered by [register_once].
+///
+/// # Safety
+///
+/// `user_data` must be the user_data of a closure registered by
+/// [register_once] with the same argument type, which has not been called.
+pub unsafe fn call_once<A>(user_data: *mut c_void, arg: A) {
+    let callback = unsafe { Box::from_raw(user_data.cast::<OnceCallback<A>>()) };
+    catch_panic(move || callback(arg));
+}
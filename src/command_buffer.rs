@@ -19,6 +19,7 @@
 use super::Result;
 use super::event::Event;
 use super::memory::*;
+use super::release::check_release;
 
 #[allow(unused_imports)]
 use cl3::ext::{
@@ -33,6 +34,7 @@ use cl3::ext::{
     create_command_buffer_khr, enqueue_command_buffer_khr, finalize_command_buffer_khr,
     get_command_buffer_data_khr, get_command_buffer_info_khr,
     get_command_buffer_mutable_dispatch_data, release_command_buffer_khr,
+    retain_command_buffer_khr,
 };
 #[allow(unused_imports)]
 use cl3::types::{cl_command_queue, cl_event, cl_kernel, cl_mem, cl_uint};
@@ -57,9 +59,16 @@ impl From<CommandBuffer> for cl_command_buffer_khr {
 
 impl Drop for CommandBuffer {
     fn drop(&mut self) {
-        unsafe {
-            release_command_buffer_khr(self.buffer).expect("Error: clReleaseCommandBufferKHR")
-        };
+        check_release("clReleaseCommandBufferKHR", unsafe {
+            release_command_buffer_khr(self.buffer)
+        });
+    }
+}
+
+impl Clone for CommandBuffer {
+    /// Retain the OpenCL command-buffer, so that both CommandBuffers share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.buffer) }.expect("Error: clRetainCommandBufferKHR")
     }
 }
 
@@ -71,11 +80,31 @@ impl CommandBuffer {
         Self { buffer }
     }
 
+    /// Create a CommandBuffer from an OpenCL cl_command_buffer_khr that is
+    /// owned elsewhere.  
+    /// Calls clRetainCommandBufferKHR, so that the CommandBuffer can release
+    /// it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `buffer` must be a valid OpenCL cl_command_buffer_khr.
+    pub unsafe fn from_raw_retained(buffer: cl_command_buffer_khr) -> Result<Self> {
+        unsafe { retain_command_buffer_khr(buffer)? };
+        Ok(Self::new(buffer))
+    }
+
     /// Get the underlying OpenCL cl_command_buffer_khr.
     pub const fn get(&self) -> cl_command_buffer_khr {
         self.buffer
     }
 
+    /// Release the OpenCL command-buffer, returning any error from clReleaseCommandBufferKHR instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = mem::ManuallyDrop::new(self);
+        Ok(unsafe { release_command_buffer_khr(this.buffer) }?)
+    }
+
     /// Create a command-buffer that can record commands to the specified queues.
     pub fn create(
         queues: &[cl_command_queue],
@@ -204,6 +233,61 @@ impl CommandBuffer {
         }
     }
 
+    /// Records a command to copy a rectangular region of T objects from a
+    /// buffer object to another buffer object, on the single command-queue of
+    /// the command-buffer.
+    ///
+    /// * `src_buffer` - the buffer to copy from.
+    /// * `dst_buffer` - the buffer to copy to.
+    /// * `src_origin` - the origin of the region in src_buffer, with x in T objects.
+    /// * `dst_origin` - the origin of the region in dst_buffer, with x in T objects.
+    /// * `region` - the size of the region, with the width in T objects.
+    /// * `src_pitches` - the row and slice pitches of src_buffer in T objects,
+    ///   zero pitches are derived from the region, see [Pitches].
+    /// * `dst_pitches` - the row and slice pitches of dst_buffer in T objects.
+    /// * `sync_point_wait_list` - the sync points to complete before the copy.
+    ///
+    /// returns a Result containing the sync point of the command,
+    /// CL_INVALID_VALUE if the region is empty, a pitch is too small or the
+    /// region is not within a buffer,
+    /// or the error code from the OpenCL C API function.
+    pub fn copy_rect<T>(
+        &self,
+        src_buffer: &Buffer<T>,
+        dst_buffer: &mut Buffer<T>,
+        src_origin: Origin3,
+        dst_origin: Origin3,
+        region: Region3,
+        src_pitches: Pitches,
+        dst_pitches: Pitches,
+        sync_point_wait_list: &[cl_sync_point_khr],
+    ) -> Result<cl_sync_point_khr> {
+        let size = mem::size_of::<T>();
+        let src_pitches =
+            check_rect(src_origin, region, src_pitches, src_buffer.count()?)?.to_bytes(size)?;
+        let dst_pitches =
+            check_rect(dst_origin, region, dst_pitches, dst_buffer.count()?)?.to_bytes(size)?;
+        let src_origin = src_origin.to_byte_array(size);
+        let dst_origin = dst_origin.to_byte_array(size);
+        let region = region.to_byte_array(size);
+        unsafe {
+            self.copy_buffer_rect(
+                ptr::null_mut(),
+                ptr::null(),
+                src_buffer,
+                dst_buffer,
+                src_origin.as_ptr(),
+                dst_origin.as_ptr(),
+                region.as_ptr(),
+                src_pitches.row,
+                src_pitches.slice,
+                dst_pitches.row,
+                dst_pitches.slice,
+                sync_point_wait_list,
+            )
+        }
+    }
+
     /// Records a command to copy a buffer object to an image object.
     pub unsafe fn copy_buffer_to_image<T>(
         &self,
@@ -266,6 +350,53 @@ impl CommandBuffer {
         }
     }
 
+    /// Records a command to copy a region of an image object to another
+    /// image object with the same format, on the single command-queue of the
+    /// command-buffer.
+    ///
+    /// * `src_image` - the image to copy from.
+    /// * `dst_image` - the image to copy to.
+    /// * `src_origin` - the origin of the region in src_image in pixels, see [Origin3].
+    /// * `dst_origin` - the origin of the region in dst_image in pixels.
+    /// * `region` - the size of the region in pixels, unused dimensions must be 1.
+    /// * `sync_point_wait_list` - the sync points to complete before the copy.
+    ///
+    /// returns a Result containing the sync point of the command,
+    /// CL_INVALID_VALUE if the region is empty or not within an image,
+    /// or the error code from the OpenCL C API function.
+    pub fn copy_image_region(
+        &self,
+        src_image: &Image,
+        dst_image: &mut Image,
+        src_origin: Origin3,
+        dst_origin: Origin3,
+        region: Region3,
+        sync_point_wait_list: &[cl_sync_point_khr],
+    ) -> Result<cl_sync_point_khr> {
+        src_image.check_region(src_origin, region)?;
+        dst_image.check_region(dst_origin, region)?;
+        let src_origin = src_origin.to_array();
+        let dst_origin = dst_origin.to_array();
+        let region = region.to_array();
+        let mut sync_point = 0;
+        unsafe {
+            command_copy_image_khr(
+                self.buffer,
+                ptr::null_mut(),
+                ptr::null(),
+                src_image.get(),
+                dst_image.get_mut(),
+                src_origin.as_ptr(),
+                dst_origin.as_ptr(),
+                region.as_ptr(),
+                sync_point_wait_list,
+                &mut sync_point,
+                ptr::null_mut(),
+            )?
+        };
+        Ok(sync_point)
+    }
+
     /// Records a command to copy an image object to a buffer object.
     pub unsafe fn copy_image_to_buffer<T>(
         &self,
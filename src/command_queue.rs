@@ -21,8 +21,25 @@ use super::context::Context;
 
 use super::Result;
 use super::device::Device;
-use super::event::Event;
+#[cfg(any(
+    all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+    feature = "dynamic"
+))]
+use super::device::KnownExtension;
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+use super::event::WaitTimeoutError;
+use super::event::{Event, OwnedTransfer, PendingTransfer};
 use super::memory::*;
+use super::profiler::Profiler;
+use super::release::{check_release, release_error};
+#[cfg(any(
+    all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+    feature = "dynamic"
+))]
+use cl3::error_codes::CL_INVALID_DEVICE;
+use cl3::error_codes::{
+    CL_INVALID_OPERATION, CL_INVALID_QUEUE_PROPERTIES, CL_MEM_COPY_OVERLAP, ClError,
+};
 
 #[allow(unused_imports)]
 use cl3::d3d10;
@@ -35,12 +52,84 @@ use cl3::egl;
 #[allow(unused_imports)]
 use cl3::ext;
 use cl3::gl;
+use cl3::kernel::{CL_KERNEL_FUNCTION_NAME, get_kernel_info};
 #[allow(unused_imports)]
 use cl3::types::cl_program;
 #[allow(unused_imports)]
 use libc::{c_char, c_void, size_t};
 use std::mem;
+use std::ops::Range;
 use std::ptr;
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+use std::result;
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+use std::time::Duration;
+
+cl_flags! {
+    /// The properties of a command-queue, see:
+    /// [Queue Properties](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#legacy-queue-properties-table).
+    pub struct QueueProperties(cl_command_queue_properties) {
+        const OUT_OF_ORDER_EXEC_MODE_ENABLE = CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE;
+        const PROFILING_ENABLE = CL_QUEUE_PROFILING_ENABLE;
+        const ON_DEVICE = CL_QUEUE_ON_DEVICE;
+        const ON_DEVICE_DEFAULT = CL_QUEUE_ON_DEVICE_DEFAULT;
+    }
+}
+
+/// Check that `count` objects from `offset` are within a memory object of
+/// `len` objects, returns CL_INVALID_VALUE if they are not.
+const fn check_range(offset: usize, count: usize, len: usize) -> Result<()> {
+    match offset.checked_add(count) {
+        Some(end) if end <= len => Ok(()),
+        _ => Err(ClError(CL_INVALID_VALUE)),
+    }
+}
+
+/// Check that a memory object was not created with CL_MEM_HOST_NO_ACCESS or
+/// the host access flag that prevents the transfer, e.g. CL_MEM_HOST_WRITE_ONLY
+/// for a read, returns CL_INVALID_OPERATION if it was.
+fn check_host_access(mem: &impl ClMem, no_access: MemFlags) -> Result<()> {
+    let flags = MemFlags::from(mem.flags()?);
+    if flags.intersects(no_access | MemFlags::HOST_NO_ACCESS) {
+        Err(ClError(CL_INVALID_OPERATION))
+    } else {
+        Ok(())
+    }
+}
+
+/// The host access flags of a memory object that prevent a map with `map_flags`.
+fn map_no_access(map_flags: MapFlags) -> MemFlags {
+    let mut no_access = MemFlags::empty();
+    if map_flags.intersects(MapFlags::READ) {
+        no_access |= MemFlags::HOST_WRITE_ONLY;
+    }
+    if map_flags.intersects(MapFlags::WRITE | MapFlags::WRITE_INVALIDATE_REGION) {
+        no_access |= MemFlags::HOST_READ_ONLY;
+    }
+    no_access
+}
+
+/// The OpenCL buffer that holds a buffer's data and the byte offset of the
+/// buffer within it, i.e. the parent buffer and origin of a sub-buffer.
+fn parent_and_origin(buffer: &impl ClMem) -> Result<(cl_mem, usize)> {
+    let parent = buffer.associated_memobject()?;
+    if parent.is_null() {
+        Ok((buffer.get(), 0))
+    } else {
+        Ok((parent, buffer.offset()?))
+    }
+}
+
+/// The number of bytes in the region of a buffer rect command, for a
+/// [Profiler].
+///
+/// # Safety
+///
+/// `region` must point to the (width in bytes, height, depth) of the region.
+unsafe fn rect_bytes(region: *const size_t) -> Option<usize> {
+    let [width, height, depth] = unsafe { region.cast::<[size_t; 3]>().read() };
+    width.checked_mul(height)?.checked_mul(depth)
+}
 
 /// An OpenCL command-queue.
 ///
@@ -50,6 +139,7 @@ use std::ptr;
 pub struct CommandQueue {
     queue: cl_command_queue,
     max_work_item_dimensions: cl_uint,
+    profiler: Option<Profiler>,
 }
 
 impl From<CommandQueue> for cl_command_queue {
@@ -60,7 +150,19 @@ impl From<CommandQueue> for cl_command_queue {
 
 impl Drop for CommandQueue {
     fn drop(&mut self) {
-        unsafe { release_command_queue(self.queue).expect("Error: clReleaseCommandQueue") };
+        check_release("clReleaseCommandQueue", unsafe {
+            release_command_queue(self.queue)
+        });
+    }
+}
+
+impl Clone for CommandQueue {
+    /// Retain the OpenCL command-queue, so that both CommandQueues share it.
+    fn clone(&self) -> Self {
+        unsafe { retain_command_queue(self.queue).expect("Error: clRetainCommandQueue") };
+        let mut queue = Self::new(self.queue, self.max_work_item_dimensions);
+        queue.profiler.clone_from(&self.profiler);
+        queue
     }
 }
 
@@ -72,6 +174,7 @@ impl CommandQueue {
         Self {
             queue,
             max_work_item_dimensions,
+            profiler: None,
         }
     }
 
@@ -80,11 +183,100 @@ impl CommandQueue {
         Ok(Self::new(queue, max_work_item_dimensions))
     }
 
+    /// Create a CommandQueue from an OpenCL cl_command_queue that is owned
+    /// elsewhere.  
+    /// Calls clRetainCommandQueue, so that the CommandQueue can release it
+    /// when dropped.
+    ///
+    /// * `queue` - a valid OpenCL cl_command_queue.
+    ///
+    /// returns a Result containing the new CommandQueue
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// `queue` must be a valid OpenCL cl_command_queue.
+    pub unsafe fn from_raw_retained(queue: cl_command_queue) -> Result<Self> {
+        let device_id = isize::from(get_command_queue_info(queue, CL_QUEUE_DEVICE)?);
+        let max_work_item_dimensions =
+            Device::new(device_id as cl_device_id).max_work_item_dimensions()?;
+        unsafe { retain_command_queue(queue)? };
+        Ok(Self::new(queue, max_work_item_dimensions))
+    }
+
     /// Get the underlying OpenCL cl_command_queue.
     pub const fn get(&self) -> cl_command_queue {
         self.queue
     }
 
+    /// Release the OpenCL command-queue, returning any error from clReleaseCommandQueue instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let mut this = mem::ManuallyDrop::new(self);
+        drop(this.profiler.take());
+        Ok(unsafe { release_command_queue(this.queue) }?)
+    }
+
+    /// Attach a Profiler to record the events of the commands enqueued on the
+    /// command-queue, including kernels labelled with their function names,
+    /// or detach it with None.  
+    /// An error recording a command does not fail its enqueue method, it is
+    /// passed to the release error handler, see [crate::release].
+    ///
+    /// * `profiler` - the Profiler, which may be attached to other command-queues.
+    ///
+    /// returns an empty Result, CL_INVALID_QUEUE_PROPERTIES if the command-queue
+    /// was not created with CL_QUEUE_PROFILING_ENABLE,
+    /// or the error code from the OpenCL C API function.
+    pub fn set_profiler(&mut self, profiler: Option<Profiler>) -> Result<()> {
+        if profiler.is_some()
+            && !QueueProperties::from(self.properties()?)
+                .contains(QueueProperties::PROFILING_ENABLE)
+        {
+            return Err(ClError(CL_INVALID_QUEUE_PROPERTIES));
+        }
+        self.profiler = profiler;
+        Ok(())
+    }
+
+    /// The Profiler attached to the command-queue, if any.
+    pub const fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    /// Wrap the event of an enqueued command, recording it if a Profiler
+    /// is attached.  
+    /// The command has been enqueued, so the event is returned even if it
+    /// cannot be recorded, the error is passed to the release error handler.
+    fn profiled(&self, event: cl_event, bytes: Option<usize>) -> Event {
+        let event = Event::new(event);
+        if let Some(profiler) = &self.profiler
+            && let Err(e) = profiler.record_event(&event, None, bytes)
+        {
+            release_error("clRetainEvent", e);
+        }
+        event
+    }
+
+    /// Wrap the event of an enqueued kernel, recording it with the kernel's
+    /// function name if a Profiler is attached, see [CommandQueue::profiled].
+    fn profiled_kernel(&self, event: cl_event, kernel: cl_kernel) -> Event {
+        let event = Event::new(event);
+        if let Some(profiler) = &self.profiler {
+            let name = match get_kernel_info(kernel, CL_KERNEL_FUNCTION_NAME) {
+                Ok(name) => Some(name.into()),
+                Err(code) => {
+                    release_error("clGetKernelInfo", ClError(code));
+                    None
+                }
+            };
+            if let Err(e) = profiler.record_event(&event, name, None) {
+                release_error("clRetainEvent", e);
+            }
+        }
+        event
+    }
+
     /// Get the max_work_item_dimensions for the device that the underlying OpenCL
     /// device.
     pub const fn max_work_item_dimensions(&self) -> cl_uint {
@@ -123,10 +315,10 @@ impl CommandQueue {
     pub unsafe fn create(
         context: &Context,
         device_id: cl_device_id,
-        properties: cl_command_queue_properties,
+        properties: impl Into<QueueProperties>,
     ) -> Result<Self> {
         unsafe {
-            let queue = create_command_queue(context.get(), device_id, properties)?;
+            let queue = create_command_queue(context.get(), device_id, properties.into().bits())?;
             let device = Device::new(device_id);
             let max_work_item_dimensions = device.max_work_item_dimensions()?;
             Ok(Self::new(queue, max_work_item_dimensions))
@@ -158,7 +350,7 @@ impl CommandQueue {
     )]
     pub fn create_default(
         context: &Context,
-        properties: cl_command_queue_properties,
+        properties: impl Into<QueueProperties>,
     ) -> Result<Self> {
         unsafe { Self::create(context, context.default_device(), properties) }
     }
@@ -182,10 +374,11 @@ impl CommandQueue {
     pub unsafe fn create_with_properties(
         context: &Context,
         device_id: cl_device_id,
-        properties: cl_command_queue_properties,
+        properties: impl Into<QueueProperties>,
         queue_size: cl_uint,
     ) -> Result<Self> {
         unsafe {
+            let properties = properties.into().bits();
             let queue = if (0 < properties) || (0 < queue_size) {
                 let mut props: [cl_queue_properties; 5] = [0; 5];
 
@@ -224,7 +417,7 @@ impl CommandQueue {
     #[cfg(any(feature = "CL_VERSION_2_0", feature = "dynamic"))]
     pub fn create_default_with_properties(
         context: &Context,
-        properties: cl_command_queue_properties,
+        properties: impl Into<QueueProperties>,
         queue_size: cl_uint,
     ) -> Result<Self> {
         unsafe {
@@ -232,6 +425,76 @@ impl CommandQueue {
         }
     }
 
+    /// Create an OpenCL command-queue on a specific device, choosing the
+    /// OpenCL API function at runtime from the OpenCL version of the device.  
+    /// Calls create_command_queue_with_properties if the device supports
+    /// CL_VERSION_2_0, otherwise it calls the deprecated create_command_queue.  
+    /// Queries the device the max_work_item_dimensions.
+    ///
+    /// * `context` - a valid OpenCL context.
+    /// * `device_id` - a device or sub-device associated with context.
+    /// * `properties` - a list of properties for the command-queue, see
+    ///   [cl_command_queue_properties](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#legacy-queue-properties-table).
+    /// * `queue_size` - the size of an on-device queue, must be zero on devices
+    ///   before CL_VERSION_2_0.
+    ///
+    /// returns a Result containing the new CommandQueue
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// This is unsafe when a device is not a member of context.
+    #[cfg(any(
+        all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+        feature = "dynamic"
+    ))]
+    pub unsafe fn create_for_device_version(
+        context: &Context,
+        device_id: cl_device_id,
+        properties: impl Into<QueueProperties>,
+        queue_size: cl_uint,
+    ) -> Result<Self> {
+        unsafe {
+            if Device::new(device_id).supports_version(2, 0) {
+                Self::create_with_properties(context, device_id, properties, queue_size)
+            } else if 0 < queue_size {
+                Err(ClError(CL_INVALID_QUEUE_PROPERTIES))
+            } else {
+                Self::create(context, device_id, properties)
+            }
+        }
+    }
+
+    /// Create an OpenCL command-queue on the context default device, see
+    /// [CommandQueue::create_for_device_version].
+    ///
+    /// * `context` - a valid OpenCL context.
+    /// * `properties` - a list of properties for the command-queue, see
+    ///   [cl_command_queue_properties](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#legacy-queue-properties-table).
+    /// * `queue_size` - the size of an on-device queue, must be zero on devices
+    ///   before CL_VERSION_2_0.
+    ///
+    /// returns a Result containing the new CommandQueue
+    /// or the error code from the OpenCL C API function.
+    #[cfg(any(
+        all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+        feature = "dynamic"
+    ))]
+    pub fn create_default_for_device_version(
+        context: &Context,
+        properties: impl Into<QueueProperties>,
+        queue_size: cl_uint,
+    ) -> Result<Self> {
+        unsafe {
+            Self::create_for_device_version(
+                context,
+                context.default_device(),
+                properties,
+                queue_size,
+            )
+        }
+    }
+
     #[cfg(any(feature = "cl_khr_create_command_queue", feature = "dynamic"))]
     pub fn create_with_properties_khr(
         context: &Context,
@@ -261,6 +524,803 @@ impl CommandQueue {
         Ok(finish(self.queue)?)
     }
 
+    /// Wait for the commands in the command-queue to complete or for a
+    /// timeout to elapse, e.g. to detect a hung command.  
+    /// Waits for a marker command, see [Event::wait_timeout].
+    ///
+    /// * `timeout` - the maximum time to wait.
+    ///
+    /// returns an empty Result, WaitTimeoutError::Timeout if the timeout
+    /// elapsed first, or the error status of a terminated command or the
+    /// error code from the OpenCL C API function.
+    #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+    pub fn finish_timeout(&self, timeout: Duration) -> result::Result<(), WaitTimeoutError> {
+        let marker = unsafe { self.enqueue_marker_with_wait_list(&[])? };
+        marker.wait_timeout(timeout)
+    }
+
+    /// Read T objects from a buffer into a slice, blocking until the read has
+    /// completed.
+    ///
+    /// * `buffer` - the buffer to read from.
+    /// * `offset` - the index of the first T object in the buffer to read.
+    /// * `data` - the slice to read into, its length is the number of T objects to read.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the objects are not within
+    /// the buffer, CL_INVALID_OPERATION if the buffer was created with
+    /// CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn read<T: Copy>(&self, buffer: &Buffer<T>, offset: usize, data: &mut [T]) -> Result<()> {
+        check_host_access(buffer, MemFlags::HOST_WRITE_ONLY)?;
+        check_range(offset, data.len(), buffer.count()?)?;
+        if !data.is_empty() {
+            unsafe {
+                self.enqueue_read_buffer(
+                    buffer,
+                    CL_BLOCKING,
+                    offset * mem::size_of::<T>(),
+                    data,
+                    &[],
+                )?
+            };
+        }
+        Ok(())
+    }
+
+    /// Write T objects from a slice into a buffer, blocking until the write
+    /// has completed.
+    ///
+    /// * `buffer` - the buffer to write to.
+    /// * `offset` - the index of the first T object in the buffer to write.
+    /// * `data` - the slice to write, its length is the number of T objects to write.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the objects are not within
+    /// the buffer, CL_INVALID_OPERATION if the buffer was created with
+    /// CL_MEM_HOST_READ_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn write<T: Copy>(&self, buffer: &mut Buffer<T>, offset: usize, data: &[T]) -> Result<()> {
+        check_host_access(buffer, MemFlags::HOST_READ_ONLY)?;
+        check_range(offset, data.len(), buffer.count()?)?;
+        if !data.is_empty() {
+            unsafe {
+                self.enqueue_write_buffer(
+                    buffer,
+                    CL_BLOCKING,
+                    offset * mem::size_of::<T>(),
+                    data,
+                    &[],
+                )?
+            };
+        }
+        Ok(())
+    }
+
+    /// Read T objects from a buffer into a slice without blocking.  
+    /// The returned [PendingTransfer] borrows the slice until the read has
+    /// completed and waits for the read to complete when it is dropped.
+    ///
+    /// * `buffer` - the buffer to read from.
+    /// * `offset` - the index of the first T object in the buffer to read.
+    /// * `data` - the slice to read into, its length is the number of T objects to read.
+    /// * `event_wait_list` - the events to complete before the read.
+    ///
+    /// returns a Result containing the PendingTransfer, CL_INVALID_VALUE if
+    /// the objects are not within the buffer, CL_INVALID_OPERATION if the
+    /// buffer was created with CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The PendingTransfer must be dropped or waited on, not leaked,
+    /// e.g. by `mem::forget`, since the slice may be written after it has
+    /// been released. Use [CommandQueue::read_async_owned] if that cannot
+    /// be guaranteed.
+    pub unsafe fn read_async<'a, T: Copy>(
+        &self,
+        buffer: &Buffer<T>,
+        offset: usize,
+        data: &'a mut [T],
+        event_wait_list: &[cl_event],
+    ) -> Result<PendingTransfer<'a, T>> {
+        check_host_access(buffer, MemFlags::HOST_WRITE_ONLY)?;
+        check_range(offset, data.len(), buffer.count()?)?;
+        let event = if data.is_empty() {
+            None
+        } else {
+            Some(unsafe {
+                self.enqueue_read_buffer(
+                    buffer,
+                    CL_NON_BLOCKING,
+                    offset * mem::size_of::<T>(),
+                    data,
+                    event_wait_list,
+                )?
+            })
+        };
+        Ok(PendingTransfer::new(event))
+    }
+
+    /// Write T objects from a slice into a buffer without blocking.  
+    /// The returned [PendingTransfer] borrows the slice until the write has
+    /// completed and waits for the write to complete when it is dropped.
+    ///
+    /// * `buffer` - the buffer to write to.
+    /// * `offset` - the index of the first T object in the buffer to write.
+    /// * `data` - the slice to write, its length is the number of T objects to write.
+    /// * `event_wait_list` - the events to complete before the write.
+    ///
+    /// returns a Result containing the PendingTransfer, CL_INVALID_VALUE if
+    /// the objects are not within the buffer, CL_INVALID_OPERATION if the
+    /// buffer was created with CL_MEM_HOST_READ_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The PendingTransfer must be dropped or waited on, not leaked,
+    /// e.g. by `mem::forget`, since the slice may be read after it has
+    /// been released. Use [CommandQueue::write_async_owned] if that cannot
+    /// be guaranteed.
+    pub unsafe fn write_async<'a, T: Copy>(
+        &self,
+        buffer: &mut Buffer<T>,
+        offset: usize,
+        data: &'a [T],
+        event_wait_list: &[cl_event],
+    ) -> Result<PendingTransfer<'a, T>> {
+        check_host_access(buffer, MemFlags::HOST_READ_ONLY)?;
+        check_range(offset, data.len(), buffer.count()?)?;
+        let event = if data.is_empty() {
+            None
+        } else {
+            Some(unsafe {
+                self.enqueue_write_buffer(
+                    buffer,
+                    CL_NON_BLOCKING,
+                    offset * mem::size_of::<T>(),
+                    data,
+                    event_wait_list,
+                )?
+            })
+        };
+        Ok(PendingTransfer::new(event))
+    }
+
+    /// Read T objects from a buffer into a vector without blocking.  
+    /// The returned [OwnedTransfer] owns the vector until the read has
+    /// completed and gives it back from [OwnedTransfer::wait].
+    ///
+    /// * `buffer` - the buffer to read from.
+    /// * `offset` - the index of the first T object in the buffer to read.
+    /// * `data` - the vector to read into, its length is the number of T objects to read.
+    /// * `event_wait_list` - the events to complete before the read.
+    ///
+    /// returns a Result containing the OwnedTransfer, CL_INVALID_VALUE if
+    /// the objects are not within the buffer, CL_INVALID_OPERATION if the
+    /// buffer was created with CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn read_async_owned<T: Copy>(
+        &self,
+        buffer: &Buffer<T>,
+        offset: usize,
+        mut data: Vec<T>,
+        event_wait_list: &[cl_event],
+    ) -> Result<OwnedTransfer<T>> {
+        // Leaking an OwnedTransfer leaks the vector, so it cannot be freed early
+        let event =
+            unsafe { self.read_async(buffer, offset, &mut data, event_wait_list)? }.into_event();
+        Ok(OwnedTransfer::new(event, data))
+    }
+
+    /// Write T objects from a vector into a buffer without blocking.  
+    /// The returned [OwnedTransfer] owns the vector until the write has
+    /// completed and gives it back from [OwnedTransfer::wait].
+    ///
+    /// * `buffer` - the buffer to write to.
+    /// * `offset` - the index of the first T object in the buffer to write.
+    /// * `data` - the vector to write, its length is the number of T objects to write.
+    /// * `event_wait_list` - the events to complete before the write.
+    ///
+    /// returns a Result containing the OwnedTransfer, CL_INVALID_VALUE if
+    /// the objects are not within the buffer, CL_INVALID_OPERATION if the
+    /// buffer was created with CL_MEM_HOST_READ_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn write_async_owned<T: Copy>(
+        &self,
+        buffer: &mut Buffer<T>,
+        offset: usize,
+        data: Vec<T>,
+        event_wait_list: &[cl_event],
+    ) -> Result<OwnedTransfer<T>> {
+        let event =
+            unsafe { self.write_async(buffer, offset, &data, event_wait_list)? }.into_event();
+        Ok(OwnedTransfer::new(event, data))
+    }
+
+    /// Copy T objects from one buffer to another, blocking until the copy has
+    /// completed.
+    ///
+    /// * `src_buffer` - the buffer to copy from.
+    /// * `dst_buffer` - the buffer to copy to.
+    /// * `src_offset` - the index of the first T object in src_buffer to copy.
+    /// * `dst_offset` - the index of the first T object in dst_buffer to copy to.
+    /// * `count` - the number of T objects to copy.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the objects are not within
+    /// the buffers, CL_MEM_COPY_OVERLAP if the buffers are the same buffer or
+    /// sub-buffers of the same buffer and the source and destination overlap,
+    /// or the error code from the OpenCL C API function.
+    pub fn copy<T: Copy>(
+        &self,
+        src_buffer: &Buffer<T>,
+        dst_buffer: &mut Buffer<T>,
+        src_offset: usize,
+        dst_offset: usize,
+        count: usize,
+    ) -> Result<()> {
+        check_range(src_offset, count, src_buffer.count()?)?;
+        check_range(dst_offset, count, dst_buffer.count()?)?;
+        let size = mem::size_of::<T>();
+        if 0 < count {
+            // Buffers that share an OpenCL buffer, see Buffer::clone, or
+            // sub-buffers of the same parent buffer
+            let (src_parent, src_origin) = parent_and_origin(src_buffer)?;
+            let (dst_parent, dst_origin) = parent_and_origin(dst_buffer)?;
+            let src_start = src_origin + src_offset * size;
+            let dst_start = dst_origin + dst_offset * size;
+            let bytes = count * size;
+            if src_parent == dst_parent
+                && src_start < dst_start + bytes
+                && dst_start < src_start + bytes
+            {
+                return Err(ClError(CL_MEM_COPY_OVERLAP));
+            }
+            let event = unsafe {
+                self.enqueue_copy_buffer(
+                    src_buffer,
+                    dst_buffer,
+                    src_offset * size,
+                    dst_offset * size,
+                    bytes,
+                    &[],
+                )?
+            };
+            event.wait()?;
+        }
+        Ok(())
+    }
+
+    /// Fill T objects in a buffer with a value, blocking until the fill has
+    /// completed.
+    ///
+    /// * `buffer` - the buffer to fill.
+    /// * `value` - the value to fill the buffer with, the size of T must be
+    ///   a power of two, up to 128 bytes.
+    /// * `offset` - the index of the first T object in the buffer to fill.
+    /// * `count` - the number of T objects to fill.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the objects are not within
+    /// the buffer or the size of T is not valid for a pattern,
+    /// or the error code from the OpenCL C API function.
+    #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+    pub fn fill<T: Copy>(
+        &self,
+        buffer: &mut Buffer<T>,
+        value: T,
+        offset: usize,
+        count: usize,
+    ) -> Result<()> {
+        let size = mem::size_of::<T>();
+        if !size.is_power_of_two() || 128 < size {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+        check_range(offset, count, buffer.count()?)?;
+        if 0 < count {
+            let event = unsafe {
+                self.enqueue_fill_buffer(buffer, &[value], offset * size, count * size, &[])?
+            };
+            event.wait()?;
+        }
+        Ok(())
+    }
+
+    /// Read a rectangular region of T objects from a buffer into a slice,
+    /// blocking until the read has completed.
+    ///
+    /// * `buffer` - the buffer to read from.
+    /// * `data` - the slice to read into.
+    /// * `buffer_origin` - the origin of the region in the buffer, with x in T objects.
+    /// * `host_origin` - the origin of the region in the slice, with x in T objects.
+    /// * `region` - the size of the region, with the width in T objects.
+    /// * `buffer_pitches` - the row and slice pitches of the buffer in T objects,
+    ///   zero pitches are derived from the region, see [Pitches].
+    /// * `host_pitches` - the row and slice pitches of the slice in T objects.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the region is empty,
+    /// a pitch is too small or the region is not within the buffer or the
+    /// slice, CL_INVALID_OPERATION if the buffer was created with
+    /// CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn read_rect<T: Copy>(
+        &self,
+        buffer: &Buffer<T>,
+        data: &mut [T],
+        buffer_origin: Origin3,
+        host_origin: Origin3,
+        region: Region3,
+        buffer_pitches: Pitches,
+        host_pitches: Pitches,
+    ) -> Result<()> {
+        check_host_access(buffer, MemFlags::HOST_WRITE_ONLY)?;
+        let size = mem::size_of::<T>();
+        let buffer_pitches =
+            check_rect(buffer_origin, region, buffer_pitches, buffer.count()?)?.to_bytes(size)?;
+        let host_pitches =
+            check_rect(host_origin, region, host_pitches, data.len())?.to_bytes(size)?;
+        let buffer_origin = buffer_origin.to_byte_array(size);
+        let host_origin = host_origin.to_byte_array(size);
+        let region = region.to_byte_array(size);
+        unsafe {
+            self.enqueue_read_buffer_rect(
+                buffer,
+                CL_BLOCKING,
+                buffer_origin.as_ptr(),
+                host_origin.as_ptr(),
+                region.as_ptr(),
+                buffer_pitches.row,
+                buffer_pitches.slice,
+                host_pitches.row,
+                host_pitches.slice,
+                data.as_mut_ptr().cast::<c_void>(),
+                &[],
+            )?
+        };
+        Ok(())
+    }
+
+    /// Write a rectangular region of T objects from a slice into a buffer,
+    /// blocking until the write has completed.
+    ///
+    /// * `buffer` - the buffer to write to.
+    /// * `data` - the slice to write from.
+    /// * `buffer_origin` - the origin of the region in the buffer, with x in T objects.
+    /// * `host_origin` - the origin of the region in the slice, with x in T objects.
+    /// * `region` - the size of the region, with the width in T objects.
+    /// * `buffer_pitches` - the row and slice pitches of the buffer in T objects,
+    ///   zero pitches are derived from the region, see [Pitches].
+    /// * `host_pitches` - the row and slice pitches of the slice in T objects.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the region is empty,
+    /// a pitch is too small or the region is not within the buffer or the
+    /// slice, CL_INVALID_OPERATION if the buffer was created with
+    /// CL_MEM_HOST_READ_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn write_rect<T: Copy>(
+        &self,
+        buffer: &mut Buffer<T>,
+        data: &[T],
+        buffer_origin: Origin3,
+        host_origin: Origin3,
+        region: Region3,
+        buffer_pitches: Pitches,
+        host_pitches: Pitches,
+    ) -> Result<()> {
+        check_host_access(buffer, MemFlags::HOST_READ_ONLY)?;
+        let size = mem::size_of::<T>();
+        let buffer_pitches =
+            check_rect(buffer_origin, region, buffer_pitches, buffer.count()?)?.to_bytes(size)?;
+        let host_pitches =
+            check_rect(host_origin, region, host_pitches, data.len())?.to_bytes(size)?;
+        let buffer_origin = buffer_origin.to_byte_array(size);
+        let host_origin = host_origin.to_byte_array(size);
+        let region = region.to_byte_array(size);
+        unsafe {
+            self.enqueue_write_buffer_rect(
+                buffer,
+                CL_BLOCKING,
+                buffer_origin.as_ptr(),
+                host_origin.as_ptr(),
+                region.as_ptr(),
+                buffer_pitches.row,
+                buffer_pitches.slice,
+                host_pitches.row,
+                host_pitches.slice,
+                data.as_ptr().cast_mut().cast::<c_void>(),
+                &[],
+            )?
+        };
+        Ok(())
+    }
+
+    /// Copy a rectangular region of T objects from one buffer to another,
+    /// blocking until the copy has completed.
+    ///
+    /// * `src_buffer` - the buffer to copy from.
+    /// * `dst_buffer` - the buffer to copy to.
+    /// * `src_origin` - the origin of the region in src_buffer, with x in T objects.
+    /// * `dst_origin` - the origin of the region in dst_buffer, with x in T objects.
+    /// * `region` - the size of the region, with the width in T objects.
+    /// * `src_pitches` - the row and slice pitches of src_buffer in T objects,
+    ///   zero pitches are derived from the region, see [Pitches].
+    /// * `dst_pitches` - the row and slice pitches of dst_buffer in T objects.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the region is empty,
+    /// a pitch is too small or the region is not within a buffer,
+    /// or the error code from the OpenCL C API function, e.g.
+    /// CL_MEM_COPY_OVERLAP if the buffers share an OpenCL buffer and the
+    /// regions overlap.
+    pub fn copy_rect<T: Copy>(
+        &self,
+        src_buffer: &Buffer<T>,
+        dst_buffer: &mut Buffer<T>,
+        src_origin: Origin3,
+        dst_origin: Origin3,
+        region: Region3,
+        src_pitches: Pitches,
+        dst_pitches: Pitches,
+    ) -> Result<()> {
+        let size = mem::size_of::<T>();
+        let src_pitches =
+            check_rect(src_origin, region, src_pitches, src_buffer.count()?)?.to_bytes(size)?;
+        let dst_pitches =
+            check_rect(dst_origin, region, dst_pitches, dst_buffer.count()?)?.to_bytes(size)?;
+        let src_origin = src_origin.to_byte_array(size);
+        let dst_origin = dst_origin.to_byte_array(size);
+        let region = region.to_byte_array(size);
+        let event = unsafe {
+            self.enqueue_copy_buffer_rect(
+                src_buffer,
+                dst_buffer,
+                src_origin.as_ptr(),
+                dst_origin.as_ptr(),
+                region.as_ptr(),
+                src_pitches.row,
+                src_pitches.slice,
+                dst_pitches.row,
+                dst_pitches.slice,
+                &[],
+            )?
+        };
+        event.wait()
+    }
+
+    /// Read a region of an image into a slice, blocking until the read has
+    /// completed.
+    ///
+    /// * `image` - the image to read from.
+    /// * `origin` - the origin of the region in pixels, see [Origin3].
+    /// * `region` - the size of the region in pixels, unused dimensions must be 1.
+    /// * `pitches` - the row and slice pitches of the slice in bytes,
+    ///   zero pitches are derived from the region, see [Pitches].
+    ///   The slice pitch must be zero for 1D and 2D images.
+    /// * `data` - the slice to read the pixels into.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the region is empty or
+    /// not within the image, a pitch is invalid or the slice is too small,
+    /// CL_INVALID_OPERATION if the image was created with
+    /// CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn read_image<T: Copy>(
+        &self,
+        image: &Image,
+        origin: Origin3,
+        region: Region3,
+        pitches: Pitches,
+        data: &mut [T],
+    ) -> Result<()> {
+        check_host_access(image, MemFlags::HOST_WRITE_ONLY)?;
+        image.check_region(origin, region)?;
+        let pitches = image.check_host_region(region, pitches, mem::size_of_val(data))?;
+        let origin = origin.to_array();
+        let region = region.to_array();
+        unsafe {
+            self.enqueue_read_image(
+                image,
+                CL_BLOCKING,
+                origin.as_ptr(),
+                region.as_ptr(),
+                pitches.row,
+                pitches.slice,
+                data.as_mut_ptr().cast::<c_void>(),
+                &[],
+            )?
+        };
+        Ok(())
+    }
+
+    /// Write a region of an image from a slice, blocking until the write has
+    /// completed.
+    ///
+    /// * `image` - the image to write to.
+    /// * `origin` - the origin of the region in pixels, see [Origin3].
+    /// * `region` - the size of the region in pixels, unused dimensions must be 1.
+    /// * `pitches` - the row and slice pitches of the slice in bytes,
+    ///   zero pitches are derived from the region, see [Pitches].
+    ///   The slice pitch must be zero for 1D and 2D images.
+    /// * `data` - the slice to write the pixels from.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the region is empty or
+    /// not within the image, a pitch is invalid or the slice is too small,
+    /// CL_INVALID_OPERATION if the image was created with
+    /// CL_MEM_HOST_READ_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    pub fn write_image<T: Copy>(
+        &self,
+        image: &mut Image,
+        origin: Origin3,
+        region: Region3,
+        pitches: Pitches,
+        data: &[T],
+    ) -> Result<()> {
+        check_host_access(image, MemFlags::HOST_READ_ONLY)?;
+        image.check_region(origin, region)?;
+        let pitches = image.check_host_region(region, pitches, mem::size_of_val(data))?;
+        let origin = origin.to_array();
+        let region = region.to_array();
+        unsafe {
+            self.enqueue_write_image(
+                image,
+                CL_BLOCKING,
+                origin.as_ptr(),
+                region.as_ptr(),
+                pitches.row,
+                pitches.slice,
+                data.as_ptr().cast_mut().cast::<c_void>(),
+                &[],
+            )?
+        };
+        Ok(())
+    }
+
+    /// Copy a region of an image to another image with the same format,
+    /// blocking until the copy has completed.
+    ///
+    /// * `src_image` - the image to copy from.
+    /// * `dst_image` - the image to copy to.
+    /// * `src_origin` - the origin of the region in src_image in pixels, see [Origin3].
+    /// * `dst_origin` - the origin of the region in dst_image in pixels.
+    /// * `region` - the size of the region in pixels, unused dimensions must be 1.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the region is empty or
+    /// not within an image,
+    /// or the error code from the OpenCL C API function.
+    pub fn copy_image(
+        &self,
+        src_image: &Image,
+        dst_image: &mut Image,
+        src_origin: Origin3,
+        dst_origin: Origin3,
+        region: Region3,
+    ) -> Result<()> {
+        src_image.check_region(src_origin, region)?;
+        dst_image.check_region(dst_origin, region)?;
+        let src_origin = src_origin.to_array();
+        let dst_origin = dst_origin.to_array();
+        let region = region.to_array();
+        let event = unsafe {
+            self.enqueue_copy_image(
+                src_image,
+                dst_image,
+                src_origin.as_ptr(),
+                dst_origin.as_ptr(),
+                region.as_ptr(),
+                &[],
+            )?
+        };
+        event.wait()
+    }
+
+    /// Map T objects of a buffer for reading, blocking until they are mapped.
+    ///
+    /// * `buffer` - the buffer to map.
+    /// * `range` - the indices of the T objects in the buffer to map.
+    ///
+    /// returns a Result containing a MappedBuffer that derefs to a slice of the
+    /// T objects and unmaps them when dropped, CL_INVALID_VALUE if the range is
+    /// empty or not within the buffer, CL_INVALID_OPERATION if the buffer was
+    /// created with CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The borrow of `buffer` does not cover its clones, see [Buffer::clone],
+    /// or sub-buffers, which share its OpenCL buffer.
+    /// So the mapped T objects must not be written to, or mapped for writing,
+    /// through a clone or sub-buffer until the MappedBuffer has been dropped,
+    /// e.g. by [CommandQueue::write] or [CommandQueue::map_buffer_mut].
+    pub unsafe fn map_buffer<'a, T: Copy>(
+        &'a self,
+        buffer: &'a Buffer<T>,
+        range: Range<usize>,
+    ) -> Result<MappedBuffer<'a, T>> {
+        let mapping = self.map_buffer_range(buffer, MapFlags::READ, &range)?;
+        unsafe { MappedBuffer::new(mapping, range.len()) }
+    }
+
+    /// Map T objects of a buffer for writing, blocking until they are mapped.
+    ///
+    /// * `buffer` - the buffer to map.
+    /// * `map_flags` - CL_MAP_WRITE, optionally with CL_MAP_READ, or
+    ///   CL_MAP_WRITE_INVALIDATE_REGION, in which case the values of the
+    ///   mapped T objects are undefined until they are written.
+    /// * `range` - the indices of the T objects in the buffer to map.
+    ///
+    /// returns a Result containing a MappedBufferMut that derefs to a mutable
+    /// slice of the T objects and unmaps them when dropped, CL_INVALID_VALUE if
+    /// the map flags do not include a write flag or the range is empty or not
+    /// within the buffer, CL_INVALID_OPERATION if the buffer was created with a
+    /// host access flag that prevents the map,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The mutable borrow of `buffer` does not cover its clones, see
+    /// [Buffer::clone], or sub-buffers, which share its OpenCL buffer.
+    /// So the mapped T objects must not be accessed, or mapped, through a
+    /// clone or sub-buffer until the MappedBufferMut has been dropped,
+    /// e.g. by [CommandQueue::read] or [CommandQueue::map_buffer].
+    pub unsafe fn map_buffer_mut<'a, T: Copy>(
+        &'a self,
+        buffer: &'a mut Buffer<T>,
+        map_flags: MapFlags,
+        range: Range<usize>,
+    ) -> Result<MappedBufferMut<'a, T>> {
+        if !map_flags.intersects(MapFlags::WRITE | MapFlags::WRITE_INVALIDATE_REGION) {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+        let mapping = self.map_buffer_range(buffer, map_flags, &range)?;
+        unsafe { MappedBufferMut::new(mapping, range.len()) }
+    }
+
+    /// Map a range of T objects of a buffer, blocking until they are mapped.
+    fn map_buffer_range<T>(
+        &self,
+        buffer: &Buffer<T>,
+        map_flags: MapFlags,
+        range: &Range<usize>,
+    ) -> Result<Mapping<'_>> {
+        check_host_access(buffer, map_no_access(map_flags))?;
+        if range.is_empty() {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+        check_range(range.start, range.len(), buffer.count()?)?;
+
+        let size = mem::size_of::<T>();
+        let mut mapped: cl_mem = ptr::null_mut();
+        let _event = unsafe {
+            self.enqueue_map_buffer(
+                buffer,
+                CL_BLOCKING,
+                map_flags,
+                range.start * size,
+                range.len() * size,
+                &mut mapped,
+                &[],
+            )?
+        };
+        Ok(unsafe { Mapping::new(self, buffer.get(), mapped) })
+    }
+
+    /// Map a region of an image for reading, blocking until it is mapped.
+    ///
+    /// * `image` - the image to map.
+    /// * `origin` - the (x, y, z) offset in pixels of the region, or the
+    ///   (x, image index) offset for a 1D image array and
+    ///   (x, y, image index) offset for a 2D image array.
+    /// * `region` - the (width, height, depth) in pixels of the region,
+    ///   unused dimensions must be 1.
+    ///
+    /// returns a Result containing a MappedImage that derefs to the bytes of
+    /// the region and unmaps it when dropped, CL_INVALID_VALUE if the region
+    /// is empty or not within the image, CL_INVALID_OPERATION if the image was
+    /// created with CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The borrow of `image` does not cover its clones, which share its
+    /// OpenCL image.
+    /// So the mapped region must not be written to, or mapped for writing,
+    /// through a clone until the MappedImage has been dropped,
+    /// e.g. by [CommandQueue::write_image] or [CommandQueue::map_image_mut].
+    pub unsafe fn map_image<'a>(
+        &'a self,
+        image: &'a Image,
+        origin: impl Into<Origin3>,
+        region: impl Into<Region3>,
+    ) -> Result<MappedImage<'a>> {
+        let (mapping, layout) =
+            self.map_image_region(image, MapFlags::READ, origin.into(), region.into())?;
+        unsafe { MappedImage::new(mapping, layout) }
+    }
+
+    /// Map a region of an image for writing, blocking until it is mapped.
+    ///
+    /// * `image` - the image to map.
+    /// * `map_flags` - CL_MAP_WRITE, optionally with CL_MAP_READ, or
+    ///   CL_MAP_WRITE_INVALIDATE_REGION, in which case the values of the
+    ///   mapped pixels are undefined until they are written.
+    /// * `origin` - the (x, y, z) offset in pixels of the region, see [CommandQueue::map_image].
+    /// * `region` - the (width, height, depth) in pixels of the region,
+    ///   unused dimensions must be 1.
+    ///
+    /// returns a Result containing a MappedImageMut that derefs to the mutable
+    /// bytes of the region and unmaps it when dropped, CL_INVALID_VALUE if the
+    /// map flags do not include a write flag or the region is empty or not
+    /// within the image, CL_INVALID_OPERATION if the image was created with a
+    /// host access flag that prevents the map,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The mutable borrow of `image` does not cover its clones, which share
+    /// its OpenCL image.
+    /// So the mapped region must not be accessed, or mapped, through a clone
+    /// until the MappedImageMut has been dropped,
+    /// e.g. by [CommandQueue::read_image] or [CommandQueue::map_image].
+    pub unsafe fn map_image_mut<'a>(
+        &'a self,
+        image: &'a mut Image,
+        map_flags: MapFlags,
+        origin: impl Into<Origin3>,
+        region: impl Into<Region3>,
+    ) -> Result<MappedImageMut<'a>> {
+        if !map_flags.intersects(MapFlags::WRITE | MapFlags::WRITE_INVALIDATE_REGION) {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+        let (mapping, layout) =
+            self.map_image_region(image, map_flags, origin.into(), region.into())?;
+        unsafe { MappedImageMut::new(mapping, layout) }
+    }
+
+    /// Map a region of an image, blocking until it is mapped.
+    fn map_image_region(
+        &self,
+        image: &Image,
+        map_flags: MapFlags,
+        origin: Origin3,
+        region: Region3,
+    ) -> Result<(Mapping<'_>, MappedImageLayout)> {
+        check_host_access(image, map_no_access(map_flags))?;
+        image.check_region(origin, region)?;
+        let row_len = region.width * image.element_size()?;
+        let is_1d_array = CL_MEM_OBJECT_IMAGE1D_ARRAY == image.mem_type()?;
+
+        let origin_array = origin.to_array();
+        let region_array = region.to_array();
+        let mut row_pitch: size_t = 0;
+        let mut slice_pitch: size_t = 0;
+        let mut mapped: cl_mem = ptr::null_mut();
+        let _event = unsafe {
+            self.enqueue_map_image(
+                image,
+                CL_BLOCKING,
+                map_flags,
+                origin_array.as_ptr(),
+                region_array.as_ptr(),
+                &mut row_pitch,
+                &mut slice_pitch,
+                &mut mapped,
+                &[],
+            )?
+        };
+        let mapping = unsafe { Mapping::new(self, image.get(), mapped) };
+
+        // The images of a 1D image array are separated by the slice pitch
+        let len = if is_1d_array {
+            (region.height - 1) * slice_pitch + row_len
+        } else {
+            (region.depth - 1) * slice_pitch + (region.height - 1) * row_pitch + row_len
+        };
+        Ok((
+            mapping,
+            MappedImageLayout {
+                len,
+                row_pitch,
+                slice_pitch,
+            },
+        ))
+    }
+
     pub unsafe fn enqueue_read_buffer<T>(
         &self,
         buffer: &Buffer<T>,
@@ -284,7 +1344,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(mem::size_of_val(data))))
         }
     }
 
@@ -323,7 +1383,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, rect_bytes(region)))
         }
     }
 
@@ -351,7 +1411,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(mem::size_of_val(data))))
         }
     }
 
@@ -389,7 +1449,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, rect_bytes(region)))
         }
     }
 
@@ -418,7 +1478,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(size)))
         }
     }
 
@@ -446,7 +1506,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(size)))
         }
     }
 
@@ -482,7 +1542,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, rect_bytes(region)))
         }
     }
 
@@ -514,7 +1574,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -546,7 +1606,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -573,7 +1633,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -601,7 +1661,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -629,7 +1689,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -657,7 +1717,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -665,7 +1725,7 @@ impl CommandQueue {
         &self,
         buffer: &Buffer<T>,
         blocking_map: cl_bool,
-        map_flags: cl_map_flags,
+        map_flags: impl Into<MapFlags>,
         offset: size_t,
         size: size_t,
         buffer_ptr: &mut cl_mem,
@@ -676,7 +1736,7 @@ impl CommandQueue {
                 self.queue,
                 buffer.get(),
                 blocking_map,
-                map_flags,
+                map_flags.into().bits(),
                 offset,
                 size,
                 buffer_ptr,
@@ -687,7 +1747,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(size)))
         }
     }
 
@@ -695,7 +1755,7 @@ impl CommandQueue {
         &self,
         image: &Image,
         blocking_map: cl_bool,
-        map_flags: cl_map_flags,
+        map_flags: impl Into<MapFlags>,
         origin: *const size_t,
         region: *const size_t,
         image_row_pitch: *mut size_t,
@@ -708,7 +1768,7 @@ impl CommandQueue {
                 self.queue,
                 image.get(),
                 blocking_map,
-                map_flags,
+                map_flags.into().bits(),
                 origin,
                 region,
                 image_row_pitch,
@@ -721,7 +1781,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -743,7 +1803,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -768,7 +1828,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -793,7 +1853,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -821,7 +1881,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled_kernel(event, kernel))
         }
     }
 
@@ -854,7 +1914,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled_kernel(event, kernel))
         }
     }
 
@@ -887,7 +1947,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -906,7 +1966,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -925,7 +1985,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -958,7 +2018,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -985,7 +2045,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(size)))
         }
     }
 
@@ -1011,7 +2071,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, Some(size)))
         }
     }
 
@@ -1019,7 +2079,7 @@ impl CommandQueue {
     pub unsafe fn enqueue_svm_map<T>(
         &self,
         blocking_map: cl_bool,
-        flags: cl_map_flags,
+        flags: impl Into<MapFlags>,
         svm: &mut [T],
         event_wait_list: &[cl_event],
     ) -> Result<Event> {
@@ -1027,7 +2087,7 @@ impl CommandQueue {
             let event = enqueue_svm_map(
                 self.queue,
                 blocking_map,
-                flags,
+                flags.into().bits(),
                 svm.as_mut_ptr() as *mut c_void,
                 mem::size_of_val(svm),
                 event_wait_list.len() as cl_uint,
@@ -1037,7 +2097,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1059,7 +2119,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1085,7 +2145,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1106,7 +2166,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1127,7 +2187,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1150,7 +2210,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1173,7 +2233,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1196,7 +2256,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1219,7 +2279,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1242,7 +2302,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1265,7 +2325,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1290,7 +2350,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1315,7 +2375,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1338,7 +2398,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1361,7 +2421,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1392,7 +2452,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1421,7 +2481,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1450,7 +2510,7 @@ impl CommandQueue {
                     ptr::null()
                 },
             )?;
-            Ok(Event::new(event))
+            Ok(self.profiled(event, None))
         }
     }
 
@@ -1462,6 +2522,19 @@ impl CommandQueue {
         Ok(isize::from(get_command_queue_info(self.queue, CL_QUEUE_DEVICE)?) as cl_device_id)
     }
 
+    /// The Context of the command-queue, retained so that it can outlive the
+    /// CommandQueue.
+    pub fn retained_context(&self) -> Result<Context> {
+        unsafe { Context::from_raw_retained(self.context()?) }
+    }
+
+    /// The [Device] of the command-queue.  
+    /// Note: a Device is not reference counted, so it must not be used after
+    /// a sub-device that it refers to has been released.
+    pub fn device_object(&self) -> Result<Device> {
+        Ok(Device::new(self.device()?))
+    }
+
     pub fn reference_count(&self) -> Result<cl_uint> {
         Ok(get_command_queue_info(self.queue, CL_QUEUE_REFERENCE_COUNT)?.into())
     }
@@ -1495,6 +2568,273 @@ impl CommandQueue {
     }
 }
 
+/// The cl_khr_priority_hints priority of a command-queue, see
+/// [CommandQueueBuilder::set_priority_khr].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueuePriority {
+    High,
+    Medium,
+    Low,
+}
+
+impl QueuePriority {
+    /// The CL_QUEUE_PRIORITY_KHR property value.
+    pub const fn value(self) -> ext::cl_queue_priority_khr {
+        match self {
+            Self::High => ext::CL_QUEUE_PRIORITY_HIGH_KHR,
+            Self::Medium => ext::CL_QUEUE_PRIORITY_MED_KHR,
+            Self::Low => ext::CL_QUEUE_PRIORITY_LOW_KHR,
+        }
+    }
+}
+
+/// The cl_khr_throttle_hints throttle level of a command-queue, see
+/// [CommandQueueBuilder::set_throttle_khr].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueueThrottle {
+    High,
+    Medium,
+    Low,
+}
+
+impl QueueThrottle {
+    /// The CL_QUEUE_THROTTLE_KHR property value.
+    pub const fn value(self) -> ext::cl_queue_throttle_khr {
+        match self {
+            Self::High => ext::CL_QUEUE_THROTTLE_HIGH_KHR,
+            Self::Medium => ext::CL_QUEUE_THROTTLE_MED_KHR,
+            Self::Low => ext::CL_QUEUE_THROTTLE_LOW_KHR,
+        }
+    }
+}
+
+/// A builder for a [CommandQueue] with typed queue properties.
+///
+/// Replaces a null terminated list of cl_queue_properties, see
+/// [Queue Properties](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#queue-properties-table).
+///
+/// [CommandQueueBuilder::build] chooses the OpenCL API function at runtime
+/// from the OpenCL version of the device:
+/// * clCreateCommandQueueWithProperties on CL_VERSION_2_0 devices,
+/// * the deprecated clCreateCommandQueue on earlier devices if only the
+///   [QueueProperties] flags are set,
+/// * clCreateCommandQueueWithPropertiesKHR on earlier devices that support
+///   cl_khr_create_command_queue for any other properties,
+///   e.g. the cl_khr_priority_hints priority.
+///
+/// Note: cl_qcom_perf_hint is a context property, see
+/// [ContextBuilder::set_perf_hint_qcom](crate::context::ContextBuilder::set_perf_hint_qcom).
+#[derive(Clone, Debug, Default)]
+pub struct CommandQueueBuilder {
+    device: Option<cl_device_id>,
+    flags: QueueProperties,
+    queue_size: Option<cl_uint>,
+    properties: Vec<(cl_queue_properties, cl_queue_properties)>,
+}
+
+impl CommandQueueBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the CommandQueue on this device, e.g. a [Device] or a
+    /// [SubDevice](crate::device::SubDevice), instead of the context default
+    /// device.
+    pub const fn set_device(&mut self, device: &Device) -> &mut Self {
+        self.device = Some(device.id());
+        self
+    }
+
+    /// CL_QUEUE_PROPERTIES: set all the queue properties flags, replacing
+    /// any flags set by the other setters.
+    pub fn set_properties(&mut self, properties: impl Into<QueueProperties>) -> &mut Self {
+        self.flags = properties.into();
+        self
+    }
+
+    fn set_flag(&mut self, flag: QueueProperties, enable: bool) -> &mut Self {
+        self.flags = if enable {
+            self.flags | flag
+        } else {
+            QueueProperties::from_bits(self.flags.bits() & !flag.bits())
+        };
+        self
+    }
+
+    /// CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE: whether commands may be
+    /// executed out of order.
+    pub fn set_out_of_order(&mut self, enable: bool) -> &mut Self {
+        self.set_flag(QueueProperties::OUT_OF_ORDER_EXEC_MODE_ENABLE, enable)
+    }
+
+    /// CL_QUEUE_PROFILING_ENABLE: whether the profiling of commands is enabled.
+    pub fn set_profiling(&mut self, enable: bool) -> &mut Self {
+        self.set_flag(QueueProperties::PROFILING_ENABLE, enable)
+    }
+
+    /// CL_QUEUE_ON_DEVICE: whether the CommandQueue is an on-device queue.  
+    /// An on-device queue must also be out of order, see
+    /// [CommandQueueBuilder::set_out_of_order].
+    pub fn set_on_device(&mut self, enable: bool) -> &mut Self {
+        self.set_flag(QueueProperties::ON_DEVICE, enable)
+    }
+
+    /// CL_QUEUE_ON_DEVICE_DEFAULT: whether the CommandQueue is the default
+    /// on-device queue, enabling CL_QUEUE_ON_DEVICE if enabled.
+    pub fn set_on_device_default(&mut self, enable: bool) -> &mut Self {
+        if enable {
+            self.set_on_device(true);
+        }
+        self.set_flag(QueueProperties::ON_DEVICE_DEFAULT, enable)
+    }
+
+    /// CL_QUEUE_SIZE: the size in bytes of an on-device queue.
+    pub const fn set_queue_size(&mut self, queue_size: cl_uint) -> &mut Self {
+        self.queue_size = Some(queue_size);
+        self
+    }
+
+    /// Set a queue property, replacing any previous value of the property.  
+    /// Used for the properties that do not have their own setter, e.g.
+    /// vendor extension properties.
+    pub fn set_property(
+        &mut self,
+        name: cl_queue_properties,
+        value: cl_queue_properties,
+    ) -> &mut Self {
+        if let Some(property) = self.properties.iter_mut().find(|(key, _)| *key == name) {
+            property.1 = value;
+        } else {
+            self.properties.push((name, value));
+        }
+        self
+    }
+
+    /// CL_QUEUE_PRIORITY_KHR: the cl_khr_priority_hints priority of the
+    /// CommandQueue.
+    pub fn set_priority_khr(&mut self, priority: QueuePriority) -> &mut Self {
+        self.set_property(
+            ext::CL_QUEUE_PRIORITY_KHR as cl_queue_properties,
+            priority.value() as cl_queue_properties,
+        )
+    }
+
+    /// CL_QUEUE_THROTTLE_KHR: the cl_khr_throttle_hints throttle level of
+    /// the CommandQueue.
+    pub fn set_throttle_khr(&mut self, throttle: QueueThrottle) -> &mut Self {
+        self.set_property(
+            ext::CL_QUEUE_THROTTLE_KHR as cl_queue_properties,
+            throttle.value() as cl_queue_properties,
+        )
+    }
+
+    /// The null terminated list of cl_queue_properties for
+    /// clCreateCommandQueueWithProperties, empty if no properties have been set.
+    pub fn properties(&self) -> Vec<cl_queue_properties> {
+        let mut properties: Vec<cl_queue_properties> = Vec::new();
+        if !self.flags.is_empty() {
+            properties.push(CL_QUEUE_PROPERTIES as cl_queue_properties);
+            properties.push(self.flags.bits() as cl_queue_properties);
+        }
+        if let Some(queue_size) = self.queue_size {
+            properties.push(CL_QUEUE_SIZE as cl_queue_properties);
+            properties.push(queue_size as cl_queue_properties);
+        }
+        properties.extend(
+            self.properties
+                .iter()
+                .flat_map(|(name, value)| [*name, *value]),
+        );
+
+        if !properties.is_empty() {
+            properties.push(0);
+        }
+        properties
+    }
+
+    /// Whether only the [QueueProperties] flags have been set, so that the
+    /// CommandQueue can be created by the deprecated clCreateCommandQueue.
+    #[cfg(any(
+        all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+        feature = "dynamic"
+    ))]
+    const fn is_legacy(&self) -> bool {
+        self.queue_size.is_none() && self.properties.is_empty()
+    }
+
+    /// Create the CommandQueue.
+    ///
+    /// * `context` - the Context, which must contain the device.
+    ///
+    /// returns a Result containing the new CommandQueue
+    /// or the error code from the OpenCL C API function:
+    /// CL_INVALID_DEVICE if the device is not a member of the context,
+    /// CL_INVALID_QUEUE_PROPERTIES if the device does not support
+    /// CL_VERSION_2_0 or cl_khr_create_command_queue and properties other
+    /// than the [QueueProperties] flags have been set.
+    #[cfg(any(
+        all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+        feature = "dynamic"
+    ))]
+    pub fn build(&self, context: &Context) -> Result<CommandQueue> {
+        let device_id = self.device.unwrap_or_else(|| context.default_device());
+        if !context.devices().contains(&device_id) {
+            return Err(ClError(CL_INVALID_DEVICE));
+        }
+
+        let device = Device::new(device_id);
+        let properties = self.properties();
+        let properties_ptr = if properties.is_empty() {
+            ptr::null()
+        } else {
+            properties.as_ptr()
+        };
+
+        let queue = if device.supports_version(2, 0) {
+            unsafe {
+                create_command_queue_with_properties(context.get(), device_id, properties_ptr)?
+            }
+        } else if self.is_legacy() {
+            unsafe { create_command_queue(context.get(), device_id, self.flags.bits())? }
+        } else if device
+            .extension_set()
+            .is_ok_and(|extensions| extensions.supports(KnownExtension::KhrCreateCommandQueue))
+        {
+            create_command_queue_khr(context, device_id, properties_ptr)?
+        } else {
+            return Err(ClError(CL_INVALID_QUEUE_PROPERTIES));
+        };
+
+        let max_work_item_dimensions = device.max_work_item_dimensions()?;
+        Ok(CommandQueue::new(queue, max_work_item_dimensions))
+    }
+}
+
+/// Call clCreateCommandQueueWithPropertiesKHR for [CommandQueueBuilder::build].
+#[cfg(any(
+    all(feature = "CL_VERSION_1_2", feature = "CL_VERSION_2_0"),
+    feature = "dynamic"
+))]
+fn create_command_queue_khr(
+    context: &Context,
+    device_id: cl_device_id,
+    properties: *const cl_queue_properties,
+) -> Result<cl_command_queue> {
+    #[cfg(any(feature = "cl_khr_create_command_queue", feature = "dynamic"))]
+    {
+        Ok(ext::create_command_queue_with_properties_khr(
+            context.get(),
+            device_id,
+            properties,
+        )?)
+    }
+    #[cfg(not(any(feature = "cl_khr_create_command_queue", feature = "dynamic")))]
+    {
+        let _ = (context, device_id, properties);
+        Err(ClError(CL_INVALID_QUEUE_PROPERTIES))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1502,6 +2842,7 @@ mod tests {
     use crate::device::Device;
     use crate::platform::get_platforms;
     use cl3::device::CL_DEVICE_TYPE_GPU;
+    use cl3::types::cl_int;
     use libc::intptr_t;
 
     #[test]
@@ -1562,6 +2903,419 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_queue_transfers() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 16;
+        let mut a = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+        let mut b = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+        assert_eq!(ARRAY_SIZE, a.count().unwrap());
+
+        let values: Vec<cl_int> = (0..ARRAY_SIZE as cl_int).collect();
+        queue.write(&mut a, 0, &values).unwrap();
+        queue.fill(&mut b, 7, 0, ARRAY_SIZE).unwrap();
+        queue.copy(&a, &mut b, 4, 8, 4).unwrap();
+
+        let mut results = [0; ARRAY_SIZE];
+        queue.read(&b, 0, &mut results).unwrap();
+        assert_eq!([7, 7, 7, 7, 7, 7, 7, 7, 4, 5, 6, 7, 7, 7, 7, 7], results);
+
+        // Out of range transfers
+        assert_eq!(
+            CL_INVALID_VALUE,
+            queue.read(&a, 1, &mut results).unwrap_err().0
+        );
+        assert_eq!(
+            CL_INVALID_VALUE,
+            queue.write(&mut a, usize::MAX, &values).unwrap_err().0
+        );
+        assert_eq!(
+            CL_INVALID_VALUE,
+            queue.copy(&a, &mut b, 0, 8, 9).unwrap_err().0
+        );
+
+        // Overlapping copy within a shared buffer
+        let shared = a.clone();
+        assert_eq!(
+            CL_MEM_COPY_OVERLAP,
+            queue.copy(&shared, &mut a, 0, 2, 4).unwrap_err().0
+        );
+
+        // Overlapping copy between sub-buffers of the same buffer
+        let align = device.mem_base_addr_align().unwrap() as usize / 8 / mem::size_of::<cl_int>();
+        let parent = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, 4 * align, ptr::null_mut())
+                .unwrap()
+        };
+        let first = unsafe { parent.create_sub_buffer(CL_MEM_READ_WRITE, 0, 2 * align) }.unwrap();
+        let mut second =
+            unsafe { parent.create_sub_buffer(CL_MEM_READ_WRITE, align, 2 * align) }.unwrap();
+        assert_eq!(
+            CL_MEM_COPY_OVERLAP,
+            queue
+                .copy(&first, &mut second, align, 0, align)
+                .unwrap_err()
+                .0
+        );
+        queue.copy(&first, &mut second, 0, align, align).unwrap();
+
+        // Host access flags
+        let no_access = unsafe {
+            Buffer::<cl_int>::create(
+                &context,
+                CL_MEM_READ_WRITE | CL_MEM_HOST_NO_ACCESS,
+                ARRAY_SIZE,
+                ptr::null_mut(),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            CL_INVALID_OPERATION,
+            queue.read(&no_access, 0, &mut results).unwrap_err().0
+        );
+    }
+
+    #[test]
+    fn test_command_queue_rect_transfers() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        // A 4x4 grid
+        const WIDTH: usize = 4;
+        const ARRAY_SIZE: usize = WIDTH * WIDTH;
+        let mut a = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+        let mut b = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+        let grid_pitches = Pitches::new(WIDTH, 0);
+
+        let values: Vec<cl_int> = (0..ARRAY_SIZE as cl_int).collect();
+        queue.write(&mut a, 0, &values).unwrap();
+        queue.fill(&mut b, 0, 0, ARRAY_SIZE).unwrap();
+
+        // Read the 2x2 tile at (1, 1) into a packed slice
+        let tile = Region3::new_2d(2, 2);
+        let mut results = [0; 4];
+        queue
+            .read_rect(
+                &a,
+                &mut results,
+                Origin3::new(1, 1, 0),
+                Origin3::ZERO,
+                tile,
+                grid_pitches,
+                Pitches::default(),
+            )
+            .unwrap();
+        assert_eq!([5, 6, 9, 10], results);
+
+        // Copy the tile to (2, 2) of b and write it to (0, 0) of b
+        queue
+            .copy_rect(
+                &a,
+                &mut b,
+                Origin3::new(1, 1, 0),
+                Origin3::new(2, 2, 0),
+                tile,
+                grid_pitches,
+                grid_pitches,
+            )
+            .unwrap();
+        queue
+            .write_rect(
+                &mut b,
+                &results,
+                Origin3::ZERO,
+                Origin3::ZERO,
+                tile,
+                grid_pitches,
+                Pitches::default(),
+            )
+            .unwrap();
+
+        let mut grid = [0; ARRAY_SIZE];
+        queue.read(&b, 0, &mut grid).unwrap();
+        assert_eq!([5, 6, 0, 0, 9, 10, 0, 0, 0, 0, 5, 6, 0, 0, 9, 10], grid);
+
+        // The tile at (3, 3) is not within the grid
+        assert_eq!(
+            CL_INVALID_VALUE,
+            queue
+                .read_rect(
+                    &a,
+                    &mut results,
+                    Origin3::new(3, 3, 0),
+                    Origin3::ZERO,
+                    tile,
+                    grid_pitches,
+                    Pitches::default(),
+                )
+                .unwrap_err()
+                .0
+        );
+        // The host slice is too small for a row pitch of 3
+        assert_eq!(
+            CL_INVALID_VALUE,
+            queue
+                .read_rect(
+                    &a,
+                    &mut results,
+                    Origin3::ZERO,
+                    Origin3::ZERO,
+                    tile,
+                    grid_pitches,
+                    Pitches::new(3, 0),
+                )
+                .unwrap_err()
+                .0
+        );
+    }
+
+    #[test]
+    fn test_command_queue_map_buffer() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 16;
+        let mut buffer = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        {
+            let mut mapped = unsafe {
+                queue.map_buffer_mut(
+                    &mut buffer,
+                    MapFlags::WRITE_INVALIDATE_REGION,
+                    0..ARRAY_SIZE,
+                )
+            }
+            .unwrap();
+            for (i, value) in mapped.iter_mut().enumerate() {
+                *value = i as cl_int;
+            }
+        }
+
+        let mapped = unsafe { queue.map_buffer(&buffer, 4..8) }.unwrap();
+        assert_eq!([4, 5, 6, 7], *mapped);
+        assert_eq!(1, buffer.map_count().unwrap());
+        mapped.unmap().unwrap().wait().unwrap();
+        assert_eq!(0, buffer.map_count().unwrap());
+
+        // Read only maps can overlap
+        let first = unsafe { queue.map_buffer(&buffer, 0..ARRAY_SIZE) }.unwrap();
+        let second = unsafe { queue.map_buffer(&buffer, 8..ARRAY_SIZE) }.unwrap();
+        assert_eq!(first[8..], *second);
+        drop(first);
+        drop(second);
+
+        // Invalid maps
+        assert_eq!(
+            CL_INVALID_VALUE,
+            unsafe { queue.map_buffer(&buffer, 8..ARRAY_SIZE + 1) }
+                .unwrap_err()
+                .0
+        );
+        assert_eq!(
+            CL_INVALID_VALUE,
+            unsafe { queue.map_buffer(&buffer, 4..4) }.unwrap_err().0
+        );
+        assert_eq!(
+            CL_INVALID_VALUE,
+            unsafe { queue.map_buffer_mut(&mut buffer, MapFlags::READ, 0..ARRAY_SIZE) }
+                .unwrap_err()
+                .0
+        );
+        queue.finish().unwrap();
+    }
+
+    #[test]
+    fn test_command_queue_async_transfers() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 16;
+        let mut buffer = unsafe {
+            Buffer::<cl_int>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        let values: Vec<cl_int> = (0..ARRAY_SIZE as cl_int).collect();
+        let write = unsafe { queue.write_async(&mut buffer, 0, &values, &[]).unwrap() };
+        write.wait().unwrap();
+
+        let mut results = [0; ARRAY_SIZE];
+        {
+            // Dropping the transfer waits for the read to complete
+            let _read = unsafe { queue.read_async(&buffer, 0, &mut results, &[]).unwrap() };
+        }
+        assert_eq!(values.as_slice(), results.as_slice());
+
+        // An empty transfer has no event
+        let empty = unsafe {
+            queue
+                .read_async(&buffer, 0, &mut results[..0], &[])
+                .unwrap()
+        };
+        assert!(empty.event().is_none());
+        assert!(empty.is_complete().unwrap());
+
+        let reversed: Vec<cl_int> = values.iter().rev().copied().collect();
+        let write = queue
+            .write_async_owned(&mut buffer, 0, reversed.clone(), &[])
+            .unwrap();
+        let written = write.wait().unwrap();
+        assert_eq!(reversed, written);
+
+        let read = queue
+            .read_async_owned(&buffer, 0, vec![0; ARRAY_SIZE], &[])
+            .unwrap();
+        let results = read.wait().unwrap();
+        assert_eq!(reversed, results);
+
+        assert_eq!(
+            CL_INVALID_VALUE,
+            queue
+                .read_async_owned(&buffer, 1, vec![0; ARRAY_SIZE], &[])
+                .unwrap_err()
+                .0
+        );
+    }
+
+    #[test]
+    fn test_command_queue_for_device_version() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+
+        // Create a command_queue using the API supported by the device
+        let queue =
+            CommandQueue::create_default_for_device_version(&context, CL_QUEUE_PROFILING_ENABLE, 0)
+                .expect("CommandQueue::create_default_for_device_version failed");
+
+        let value = queue.device().unwrap();
+        assert!(device.id() == value);
+
+        let value = queue.properties().unwrap();
+        println!("queue.properties(): {:X}", value);
+        assert!(0 < value & CL_QUEUE_PROFILING_ENABLE);
+    }
+
+    #[test]
+    fn test_command_queue_builder() {
+        let mut builder = CommandQueueBuilder::new();
+        assert!(builder.properties().is_empty());
+
+        builder
+            .set_out_of_order(true)
+            .set_profiling(true)
+            .set_out_of_order(false)
+            .set_priority_khr(QueuePriority::Low)
+            .set_priority_khr(QueuePriority::High);
+        assert_eq!(
+            vec![
+                CL_QUEUE_PROPERTIES as cl_queue_properties,
+                CL_QUEUE_PROFILING_ENABLE as cl_queue_properties,
+                ext::CL_QUEUE_PRIORITY_KHR as cl_queue_properties,
+                ext::CL_QUEUE_PRIORITY_HIGH_KHR as cl_queue_properties,
+                0
+            ],
+            builder.properties()
+        );
+
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+
+        // Create a profiling command_queue using the API supported by the device
+        let queue = CommandQueueBuilder::new()
+            .set_device(&device)
+            .set_profiling(true)
+            .build(&context)
+            .expect("CommandQueueBuilder::build failed");
+
+        let value = queue.device().unwrap();
+        assert!(device.id() == value);
+
+        let value = queue.properties().unwrap();
+        println!("queue.properties(): {:X}", value);
+        assert!(0 < value & CL_QUEUE_PROFILING_ENABLE);
+    }
+
     #[test]
     fn test_command_queue_from_cl3() {
         let platforms = get_platforms().unwrap();
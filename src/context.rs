@@ -17,9 +17,13 @@
 pub use cl3::context;
 
 use super::Result;
-use super::device::Device;
+use super::callback;
 #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
 use super::device::SubDevice;
+use super::device::{Device, DeviceType};
+use super::memory::MemFlags;
+use super::platform::Platform;
+use super::release::check_release;
 
 #[allow(unused_imports)]
 use cl3::dx9_media_sharing;
@@ -41,10 +45,16 @@ use cl3::gl;
 #[allow(unused_imports)]
 use cl3::types::{
     cl_context, cl_context_info, cl_context_properties, cl_device_id, cl_device_svm_capabilities,
-    cl_device_type, cl_event, cl_image_format, cl_mem, cl_mem_flags, cl_mem_object_type, cl_uint,
+    cl_device_type, cl_event, cl_image_format, cl_mem, cl_mem_flags, cl_mem_object_type,
+    cl_platform_id, cl_uint,
 };
 use libc::{c_char, c_void, intptr_t, size_t};
+use std::ffi::CStr;
+use std::fmt;
+use std::mem::{self, ManuallyDrop};
 use std::ptr;
+use std::slice;
+use std::sync::Arc;
 
 /// Get the current device used by an OpenGL context.
 ///
@@ -104,7 +114,17 @@ impl From<Context> for cl_context {
 impl Drop for Context {
     fn drop(&mut self) {
         self.devices.clear();
-        unsafe { context::release_context(self.context).expect("Error: clReleaseContext") };
+        check_release("clReleaseContext", unsafe {
+            context::release_context(self.context)
+        });
+    }
+}
+
+impl Clone for Context {
+    /// Retain the OpenCL context, so that both Contexts share it.
+    fn clone(&self) -> Self {
+        unsafe { context::retain_context(self.context).expect("Error: clRetainContext") };
+        Self::new(self.context, &self.devices)
     }
 }
 
@@ -123,11 +143,41 @@ impl Context {
         Self::new(context, &[device.id()])
     }
 
+    /// Create a Context from an OpenCL cl_context that is owned elsewhere.  
+    /// Calls clRetainContext, so that the Context can release it when dropped.
+    ///
+    /// * `context` - a valid OpenCL cl_context.
+    ///
+    /// returns a Result containing the new Context
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// `context` must be a valid OpenCL cl_context.
+    pub unsafe fn from_raw_retained(context: cl_context) -> Result<Self> {
+        let dev_ptrs: Vec<intptr_t> =
+            context::get_context_info(context, context::CL_CONTEXT_DEVICES)?.into();
+        let devices = dev_ptrs
+            .iter()
+            .map(|ptr| *ptr as cl_device_id)
+            .collect::<Vec<cl_device_id>>();
+        unsafe { context::retain_context(context)? };
+        Ok(Self::new(context, &devices))
+    }
+
     /// Get the underlying OpenCL cl_context.
     pub const fn get(&self) -> cl_context {
         self.context
     }
 
+    /// Release the OpenCL context, returning any error from clReleaseContext instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let mut this = ManuallyDrop::new(self);
+        drop(mem::take(&mut this.devices));
+        Ok(unsafe { context::release_context(this.context) }?)
+    }
+
     /// Create a Context from a slice of cl_device_ids.  
     ///
     /// * `devices` - a slice of cl_device_ids for an OpenCL Platform.
@@ -200,7 +250,7 @@ impl Context {
     /// returns a Result containing the new OpenCL context
     /// or the error code from the OpenCL C API function.
     pub fn from_device_type(
-        device_type: cl_device_type,
+        device_type: impl Into<DeviceType>,
         properties: &[cl_context_properties],
         pfn_notify: Option<unsafe extern "C" fn(*const c_char, *const c_void, size_t, *mut c_void)>,
         user_data: *mut c_void,
@@ -210,8 +260,12 @@ impl Context {
         } else {
             ptr::null()
         };
-        let context =
-            context::create_context_from_type(device_type, properties_ptr, pfn_notify, user_data)?;
+        let context = context::create_context_from_type(
+            device_type.into().bits(),
+            properties_ptr,
+            pfn_notify,
+            user_data,
+        )?;
         let dev_ptrs: Vec<intptr_t> =
             context::get_context_info(context, context::CL_CONTEXT_DEVICES)?.into();
         let devices = dev_ptrs
@@ -249,12 +303,12 @@ impl Context {
     /// or the error code from the OpenCL C API function.
     pub fn get_supported_image_formats(
         &self,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         image_type: cl_mem_object_type,
     ) -> Result<Vec<cl_image_format>> {
         Ok(cl3::memory::get_supported_image_formats(
             self.context,
-            flags,
+            flags.into().bits(),
             image_type,
         )?)
     }
@@ -262,7 +316,7 @@ impl Context {
     #[cfg(any(feature = "cl_arm_import_memory", feature = "dynamic"))]
     pub unsafe fn import_memory_arm(
         &self,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         properties: *const ext::cl_import_properties_arm,
         memory: *mut c_void,
         size: size_t,
@@ -270,7 +324,7 @@ impl Context {
         unsafe {
             Ok(ext::import_memory_arm(
                 self.context,
-                flags,
+                flags.into().bits(),
                 properties,
                 memory,
                 size,
@@ -287,6 +341,13 @@ impl Context {
         self.devices[0]
     }
 
+    /// The [Device]s of the Context.  
+    /// Note: a Device is not reference counted, so it must not be used after
+    /// a sub-device that it refers to has been released.
+    pub fn device_objects(&self) -> Vec<Device> {
+        self.devices.iter().map(|id| Device::new(*id)).collect()
+    }
+
     pub const fn num_devices(&self) -> cl_uint {
         self.devices.len() as cl_uint
     }
@@ -302,6 +363,22 @@ impl Context {
             .map_err(Into::into)
     }
 
+    /// Call a closure when the OpenCL context is destroyed, after all of its
+    /// resources have been released, e.g. to free data used by its callbacks.  
+    /// The closure may be called on any thread, any panic in it is caught.
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    #[cfg(any(feature = "CL_VERSION_3_0", feature = "dynamic"))]
+    pub fn on_destroy<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        callback::register_once(
+            move |()| callback(),
+            |user_data| self.set_destructor_callback(Some(context_destructor_callback), user_data),
+        )
+    }
+
     pub fn reference_count(&self) -> Result<cl_uint> {
         Ok(context::get_context_info(self.context, context::CL_CONTEXT_REFERENCE_COUNT)?.into())
     }
@@ -374,14 +451,14 @@ impl Context {
     ))]
     pub fn get_supported_dx9_media_surface_formats_intel(
         &self,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         image_type: cl_mem_object_type,
         plane: cl_uint,
     ) -> Result<Vec<cl_uint>> {
         Ok(unsafe {
             dx9_media_sharing::get_supported_dx9_media_surface_formats_intel(
                 self.context,
-                flags,
+                flags.into().bits(),
                 image_type,
                 plane,
             )
@@ -391,27 +468,325 @@ impl Context {
     #[cfg(any(feature = "cl_khr_d3d10_sharing", feature = "dynamic"))]
     pub fn get_supported_d3d10_texture_formats_intel(
         &self,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         image_type: cl_mem_object_type,
     ) -> Result<Vec<cl_uint>> {
         Ok(unsafe {
-            d3d10::get_supported_d3d10_texture_formats_intel(self.context, flags, image_type)
+            d3d10::get_supported_d3d10_texture_formats_intel(
+                self.context,
+                flags.into().bits(),
+                image_type,
+            )
         }?)
     }
 
     #[cfg(any(feature = "cl_khr_d3d11_sharing", feature = "dynamic"))]
     pub fn get_supported_d3d11_texture_formats_intel(
         &self,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         image_type: cl_mem_object_type,
         plane: cl_uint,
     ) -> Result<Vec<cl_uint>> {
         Ok(unsafe {
-            d3d11::get_supported_d3d11_texture_formats_intel(self.context, flags, image_type, plane)
+            d3d11::get_supported_d3d11_texture_formats_intel(
+                self.context,
+                flags.into().bits(),
+                image_type,
+                plane,
+            )
         }?)
     }
 }
 
+/// A Rust closure called with the error messages reported by an OpenCL
+/// implementation for a context, see [ContextBuilder::on_error].
+type ErrorCallback = Arc<dyn Fn(&str, &[u8]) + Send + Sync>;
+
+/// The clCreateContext pfn_notify function for [ContextBuilder::on_error].
+/// `user_data` points to the ErrorCallback in an `Arc<ErrorCallback>`.
+unsafe extern "C" fn error_callback(
+    errinfo: *const c_char,
+    private_info: *const c_void,
+    cb: size_t,
+    user_data: *mut c_void,
+) {
+    callback::catch_panic(|| {
+        let callback = unsafe { &*(user_data as *const ErrorCallback) };
+        let message = if errinfo.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(errinfo) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        let private_info = if private_info.is_null() || 0 == cb {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(private_info as *const u8, cb) }
+        };
+        callback(&message, private_info);
+    });
+}
+
+/// The clSetContextDestructorCallback pfn_notify function for [Context::on_destroy].
+#[cfg(any(feature = "CL_VERSION_3_0", feature = "dynamic"))]
+unsafe extern "C" fn context_destructor_callback(_context: cl_context, user_data: *mut c_void) {
+    unsafe { callback::call_once(user_data, ()) };
+}
+
+/// The cl_qcom_perf_hint performance hint of a context, see
+/// [ContextBuilder::set_perf_hint_qcom].
+#[cfg(any(feature = "cl_qcom_perf_hint", feature = "dynamic"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PerfHint {
+    High,
+    Normal,
+    Low,
+}
+
+#[cfg(any(feature = "cl_qcom_perf_hint", feature = "dynamic"))]
+impl PerfHint {
+    /// The CL_CONTEXT_PERF_HINT_QCOM property value.
+    pub const fn value(self) -> ext::cl_perf_hint_qcom {
+        match self {
+            Self::High => ext::CL_PERF_HINT_HIGH_QCOM,
+            Self::Normal => ext::CL_PERF_HINT_NORMAL_QCOM,
+            Self::Low => ext::CL_PERF_HINT_LOW_QCOM,
+        }
+    }
+}
+
+/// A builder for a [Context] with typed context properties.
+///
+/// Replaces a null terminated list of cl_context_properties with typed
+/// properties and an `extern "C"` pfn_notify function with a Rust closure
+/// to receive the OpenCL implementation's error messages, see
+/// [Context Properties](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#context-properties-table).
+///
+/// The context is created for the devices set by [ContextBuilder::set_devices],
+/// or for the device type set by [ContextBuilder::set_device_type] if no
+/// devices are set.
+#[derive(Clone, Default)]
+pub struct ContextBuilder {
+    devices: Vec<cl_device_id>,
+    device_type: Option<DeviceType>,
+    properties: Vec<(cl_context_properties, cl_context_properties)>,
+    error_callback: Option<ErrorCallback>,
+}
+
+impl fmt::Debug for ContextBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextBuilder")
+            .field("devices", &self.devices)
+            .field("device_type", &self.device_type)
+            .field("properties", &self.properties)
+            .field("error_callback", &self.error_callback.is_some())
+            .finish()
+    }
+}
+
+impl ContextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the Context for these devices.
+    pub fn set_devices(&mut self, devices: &[cl_device_id]) -> &mut Self {
+        self.devices = devices.to_vec();
+        self
+    }
+
+    /// Create the Context for this device, e.g. a [Device] or a
+    /// [SubDevice].
+    pub fn set_device(&mut self, device: &Device) -> &mut Self {
+        self.set_devices(&[device.id()])
+    }
+
+    /// Create the Context for the devices of this type,
+    /// if no devices have been set.
+    pub fn set_device_type(&mut self, device_type: impl Into<DeviceType>) -> &mut Self {
+        self.device_type = Some(device_type.into());
+        self
+    }
+
+    /// Set a context property, replacing any previous value of the property.  
+    /// Used for the properties that do not have their own setter, e.g.
+    /// vendor extension properties.
+    pub fn set_property(
+        &mut self,
+        name: cl_context_properties,
+        value: cl_context_properties,
+    ) -> &mut Self {
+        if let Some(property) = self.properties.iter_mut().find(|(key, _)| *key == name) {
+            property.1 = value;
+        } else {
+            self.properties.push((name, value));
+        }
+        self
+    }
+
+    /// CL_CONTEXT_PLATFORM: the platform to create the Context on.
+    pub fn set_platform(&mut self, platform: &Platform) -> &mut Self {
+        self.set_property(
+            context::CL_CONTEXT_PLATFORM,
+            platform.id() as cl_context_properties,
+        )
+    }
+
+    /// CL_CONTEXT_INTEROP_USER_SYNC: whether the application is responsible
+    /// for synchronization between OpenCL and other APIs.
+    #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+    pub fn set_interop_user_sync(&mut self, user_sync: bool) -> &mut Self {
+        self.set_property(
+            context::CL_CONTEXT_INTEROP_USER_SYNC,
+            cl_context_properties::from(user_sync),
+        )
+    }
+
+    /// CL_CONTEXT_TERMINATE_KHR: whether the Context may be terminated,
+    /// see [Context::terminate].
+    #[cfg(any(feature = "cl_khr_terminate_context", feature = "dynamic"))]
+    pub fn set_terminate_khr(&mut self, terminate: bool) -> &mut Self {
+        self.set_property(
+            ext::CL_CONTEXT_TERMINATE_KHR as cl_context_properties,
+            cl_context_properties::from(terminate),
+        )
+    }
+
+    /// CL_GL_CONTEXT_KHR: the OpenGL context handle to share with.
+    #[cfg(any(feature = "cl_khr_gl_sharing", feature = "dynamic"))]
+    pub fn set_gl_context_khr(&mut self, gl_context: *mut c_void) -> &mut Self {
+        self.set_property(gl::CL_GL_CONTEXT_KHR, gl_context as cl_context_properties)
+    }
+
+    /// CL_EGL_DISPLAY_KHR: the EGLDisplay handle of the OpenGL context.
+    #[cfg(any(feature = "cl_khr_gl_sharing", feature = "dynamic"))]
+    pub fn set_egl_display_khr(&mut self, display: *mut c_void) -> &mut Self {
+        self.set_property(gl::CL_EGL_DISPLAY_KHR, display as cl_context_properties)
+    }
+
+    /// CL_GLX_DISPLAY_KHR: the X Display handle of the OpenGL context.
+    #[cfg(any(feature = "cl_khr_gl_sharing", feature = "dynamic"))]
+    pub fn set_glx_display_khr(&mut self, display: *mut c_void) -> &mut Self {
+        self.set_property(gl::CL_GLX_DISPLAY_KHR, display as cl_context_properties)
+    }
+
+    /// CL_WGL_HDC_KHR: the HDC handle of the OpenGL context.
+    #[cfg(any(feature = "cl_khr_gl_sharing", feature = "dynamic"))]
+    pub fn set_wgl_hdc_khr(&mut self, hdc: *mut c_void) -> &mut Self {
+        self.set_property(gl::CL_WGL_HDC_KHR, hdc as cl_context_properties)
+    }
+
+    /// CL_CONTEXT_D3D10_DEVICE_KHR: the Direct3D 10 device to share with.
+    #[cfg(any(feature = "cl_khr_d3d10_sharing", feature = "dynamic"))]
+    pub fn set_d3d10_device_khr(&mut self, device: *mut c_void) -> &mut Self {
+        self.set_property(
+            d3d10::CL_CONTEXT_D3D10_DEVICE_KHR as cl_context_properties,
+            device as cl_context_properties,
+        )
+    }
+
+    /// CL_CONTEXT_D3D11_DEVICE_KHR: the Direct3D 11 device to share with.
+    #[cfg(any(feature = "cl_khr_d3d11_sharing", feature = "dynamic"))]
+    pub fn set_d3d11_device_khr(&mut self, device: *mut c_void) -> &mut Self {
+        self.set_property(
+            d3d11::CL_CONTEXT_D3D11_DEVICE_KHR as cl_context_properties,
+            device as cl_context_properties,
+        )
+    }
+
+    /// CL_CONTEXT_PERF_HINT_QCOM: the cl_qcom_perf_hint performance hint for
+    /// the Context.
+    #[cfg(any(feature = "cl_qcom_perf_hint", feature = "dynamic"))]
+    pub fn set_perf_hint_qcom(&mut self, perf_hint: PerfHint) -> &mut Self {
+        self.set_property(
+            ext::CL_CONTEXT_PERF_HINT_QCOM as cl_context_properties,
+            perf_hint.value() as cl_context_properties,
+        )
+    }
+
+    /// Set a closure to receive the error messages that the OpenCL
+    /// implementation reports for the Context, with any implementation
+    /// specific binary data.  
+    /// The closure may be called on other threads and any panic in it is
+    /// caught, since it must not unwind into the OpenCL implementation.  
+    /// The closure is kept alive until the OpenCL implementation destroys the
+    /// Context. On OpenCL implementations before OpenCL 3.0, which cannot
+    /// report that a context has been destroyed, the closure is never freed.
+    pub fn on_error<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: Fn(&str, &[u8]) + Send + Sync + 'static,
+    {
+        self.error_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// The null terminated list of cl_context_properties for clCreateContext,
+    /// empty if no properties have been set.
+    pub fn properties(&self) -> Vec<cl_context_properties> {
+        if self.properties.is_empty() {
+            return Vec::new();
+        }
+
+        let mut properties: Vec<cl_context_properties> = self
+            .properties
+            .iter()
+            .flat_map(|(name, value)| [*name, *value])
+            .collect();
+        properties.push(0);
+        properties
+    }
+
+    /// Create the Context.
+    ///
+    /// returns a Result containing the new Context
+    /// or the error code from the OpenCL C API function.
+    pub fn build(&self) -> Result<Context> {
+        let properties = self.properties();
+        let callback = self
+            .error_callback
+            .as_ref()
+            .map(|callback| Arc::new(Arc::clone(callback)));
+        let (pfn_notify, user_data) =
+            callback
+                .as_ref()
+                .map_or((None, ptr::null_mut()), |callback| {
+                    (
+                        Some(error_callback as unsafe extern "C" fn(_, _, _, _)),
+                        Arc::as_ptr(callback).cast_mut().cast::<c_void>(),
+                    )
+                });
+
+        let result = if self.devices.is_empty() {
+            Context::from_device_type(
+                self.device_type.unwrap_or(DeviceType::DEFAULT),
+                &properties,
+                pfn_notify,
+                user_data,
+            )
+        } else {
+            Context::from_devices(&self.devices, &properties, pfn_notify, user_data)
+        };
+
+        // The OpenCL implementation may call the closure until the context is
+        // destroyed, so free it then, or leak it if that cannot be detected
+        match (&result, callback) {
+            #[cfg(any(feature = "CL_VERSION_3_0", feature = "dynamic"))]
+            (Ok(context), Some(callback)) => {
+                let owner = Arc::clone(&callback);
+                if context.on_destroy(move || drop(owner)).is_err() {
+                    mem::forget(callback);
+                }
+            }
+            #[cfg(not(any(feature = "CL_VERSION_3_0", feature = "dynamic")))]
+            (Ok(_), Some(callback)) => mem::forget(callback),
+            _ => {}
+        }
+
+        result
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,6 +833,79 @@ mod tests {
         println!("CL_CONTEXT_PROPERTIES: {:?}", context.properties().unwrap());
     }
 
+    #[test]
+    fn test_context_builder() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        let device = Device::new(devices[0]);
+        let mut builder = ContextBuilder::new();
+        builder
+            .set_device(&device)
+            .set_platform(platform)
+            .on_error(|message, private_info| {
+                println!(
+                    "OpenCL context error: {}, private_info len: {}",
+                    message,
+                    private_info.len()
+                )
+            });
+        assert_eq!(
+            vec![
+                context::CL_CONTEXT_PLATFORM,
+                platform.id() as cl_context_properties,
+                0
+            ],
+            builder.properties()
+        );
+
+        let context = builder.build().unwrap();
+        assert_eq!(1, context.num_devices());
+        assert_eq!(device.id(), context.default_device());
+        println!("CL_CONTEXT_PROPERTIES: {:?}", context.properties().unwrap());
+
+        let context = ContextBuilder::new()
+            .set_device_type(CL_DEVICE_TYPE_GPU)
+            .build()
+            .unwrap();
+        assert!(0 < context.num_devices());
+    }
+
+    #[test]
+    fn test_context_clone() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let ref_count = context.reference_count().unwrap();
+
+        let shared = context.clone();
+        assert_eq!(context.get(), shared.get());
+        assert_eq!(context.devices(), shared.devices());
+        assert_eq!(ref_count + 1, context.reference_count().unwrap());
+
+        let retained = unsafe { Context::from_raw_retained(context.get()).unwrap() };
+        assert_eq!(context.devices(), retained.devices());
+        assert_eq!(ref_count + 2, context.reference_count().unwrap());
+
+        drop(shared);
+        drop(retained);
+        assert_eq!(ref_count, context.reference_count().unwrap());
+    }
+
     #[test]
     fn test_context_from_device_type() {
         let properties = Vec::<cl_context_properties>::default();
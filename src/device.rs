@@ -17,14 +17,64 @@ pub use cl3::device::*;
 pub use cl3::ext::cl_device_feature_capabilities_intel;
 
 use super::Result;
-use super::platform::get_platforms;
+use super::platform::{Platform, get_platforms};
+use super::release::check_release;
+use cl3::error_codes::{CL_INVALID_DEVICE_PARTITION_COUNT, CL_INVALID_VALUE, ClError};
 #[allow(unused_imports)]
 use cl3::ext;
+use cl3::types::{cl_device_affinity_domain, cl_version};
 #[allow(unused_imports)]
 use libc::{intptr_t, size_t};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::result;
+use std::str::FromStr;
+
+cl_flags! {
+    /// The type of an OpenCL device, see:
+    /// [Device Types](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#device-types-table).
+    pub struct DeviceType(cl_device_type) {
+        const DEFAULT = CL_DEVICE_TYPE_DEFAULT;
+        const CPU = CL_DEVICE_TYPE_CPU;
+        const GPU = CL_DEVICE_TYPE_GPU;
+        const ACCELERATOR = CL_DEVICE_TYPE_ACCELERATOR;
+        const CUSTOM = CL_DEVICE_TYPE_CUSTOM;
+        const ALL = CL_DEVICE_TYPE_ALL;
+    }
+}
+
+cl_flags! {
+    /// The Shared Virtual Memory (SVM) capabilities of an OpenCL device, see:
+    /// [Device Queries](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#device-queries-table).
+    pub struct SvmCapabilities(cl_device_svm_capabilities) {
+        const COARSE_GRAIN_BUFFER = CL_DEVICE_SVM_COARSE_GRAIN_BUFFER;
+        const FINE_GRAIN_BUFFER = CL_DEVICE_SVM_FINE_GRAIN_BUFFER;
+        const FINE_GRAIN_SYSTEM = CL_DEVICE_SVM_FINE_GRAIN_SYSTEM;
+        const ATOMICS = CL_DEVICE_SVM_ATOMICS;
+    }
+}
+
+cl_flags! {
+    /// The floating point capabilities of an OpenCL device, see:
+    /// [Device Queries](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#device-queries-table).
+    pub struct FpConfig(cl_device_fp_config) {
+        const DENORM = CL_FP_DENORM;
+        const INF_NAN = CL_FP_INF_NAN;
+        const ROUND_TO_NEAREST = CL_FP_ROUND_TO_NEAREST;
+        const ROUND_TO_ZERO = CL_FP_ROUND_TO_ZERO;
+        const ROUND_TO_INF = CL_FP_ROUND_TO_INF;
+        const FMA = CL_FP_FMA;
+        const SOFT_FLOAT = CL_FP_SOFT_FLOAT;
+        const CORRECTLY_ROUNDED_DIVIDE_SQRT = CL_FP_CORRECTLY_ROUNDED_DIVIDE_SQRT;
+    }
+}
 
 /// Get the ids of all available devices of the given type.
-pub fn get_all_devices(device_type: cl_device_type) -> Result<Vec<cl_device_id>> {
+pub fn get_all_devices(device_type: impl Into<DeviceType>) -> Result<Vec<cl_device_id>> {
+    let device_type = device_type.into();
     let mut device_ids = Vec::<cl_device_id>::new();
 
     let platforms = get_platforms()?;
@@ -35,30 +85,741 @@ pub fn get_all_devices(device_type: cl_device_type) -> Result<Vec<cl_device_id>>
     Ok(device_ids)
 }
 
+/// An OpenCL version: major, minor and patch numbers.
+///
+/// OpenCL versions are returned as strings by [Device::version],
+/// [Device::opencl_c_version] and [Platform::version], e.g. "OpenCL 2.1 NEO",
+/// and as packed [cl_version]s by [Device::numeric_version] and
+/// [Device::extensions_with_version]. OpenClVersion can be created from either
+/// and compared, so that applications can decide at runtime which OpenCL API
+/// functions a device or platform supports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenClVersion {
+    pub major: cl_uint,
+    pub minor: cl_uint,
+    pub patch: cl_uint,
+}
+
+impl From<cl_version> for OpenClVersion {
+    fn from(version: cl_version) -> Self {
+        Self::from_cl_version(version)
+    }
+}
+
+impl From<OpenClVersion> for cl_version {
+    fn from(version: OpenClVersion) -> Self {
+        version.to_cl_version()
+    }
+}
+
+impl fmt::Display for OpenClVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl OpenClVersion {
+    pub const fn new(major: cl_uint, minor: cl_uint, patch: cl_uint) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Unpack a [cl_version], see: CL_VERSION_MAJOR, CL_VERSION_MINOR and
+    /// CL_VERSION_PATCH.
+    pub const fn from_cl_version(version: cl_version) -> Self {
+        Self {
+            major: version >> (CL_VERSION_MINOR_BITS + CL_VERSION_PATCH_BITS),
+            minor: (version >> CL_VERSION_PATCH_BITS) & CL_VERSION_MINOR_MASK,
+            patch: version & CL_VERSION_PATCH_MASK,
+        }
+    }
+
+    /// Pack the version into a [cl_version], see: CL_MAKE_VERSION.
+    pub const fn to_cl_version(self) -> cl_version {
+        ((self.major & CL_VERSION_MAJOR_MASK) << (CL_VERSION_MINOR_BITS + CL_VERSION_PATCH_BITS))
+            | ((self.minor & CL_VERSION_MINOR_MASK) << CL_VERSION_PATCH_BITS)
+            | (self.patch & CL_VERSION_PATCH_MASK)
+    }
+
+    /// Parse the version from an OpenCL version string, i.e. the first word
+    /// that starts with a "major.minor" number, e.g. "OpenCL 2.1 NEO",
+    /// "OpenCL C 1.2" or "3.0.0".
+    ///
+    /// returns the version or None if the string does not contain a version.
+    pub fn parse(text: &str) -> Option<Self> {
+        let number = text
+            .split_whitespace()
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+        let mut numbers = number.split('.').map(|n| {
+            let digits = n
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(n, |end| &n[..end]);
+            digits.parse::<cl_uint>().ok()
+        });
+        let major = numbers.next()??;
+        let minor = numbers.next()??;
+        let patch = numbers.next().flatten().unwrap_or_default();
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// Determine whether this version is at least major.minor.
+    pub fn is_at_least(&self, major: cl_uint, minor: cl_uint) -> bool {
+        (major, minor) <= (self.major, self.minor)
+    }
+}
+
+/// The OpenCL extensions that have cargo features in this crate.
+///
+/// Note: the cl_loader_info and cl_loader_layers features are ICD loader
+/// extensions, they are not reported by platforms or devices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KnownExtension {
+    AppleSetMemObjectDestructor,
+    AppleContextLoggingFunctions,
+    KhrIcd,
+    KhrIlProgram,
+    KhrTerminateContext,
+    KhrCreateCommandQueue,
+    ExtDeviceFission,
+    ExtMigrateMemobject,
+    QcomExtHostPtr,
+    ImgUseGrallocPtr,
+    ImgGenerateMipmap,
+    KhrSubgroups,
+    KhrSuggestedLocalWorkSize,
+    KhrExternalMemory,
+    KhrExternalSemaphore,
+    KhrExternalSemaphoreSyncFd,
+    KhrSemaphore,
+    ArmImportMemory,
+    ArmSharedVirtualMemory,
+    IntelAccelerator,
+    IntelUnifiedSharedMemory,
+    IntelCreateBufferWithProperties,
+    IntelProgramScopeHostPipe,
+    ExtImageRequirementsInfo,
+    KhrCommandBuffer,
+    KhrCommandBufferMultiDevice,
+    KhrCommandBufferMutableDispatch,
+    KhrGlSharing,
+    KhrGlEvent,
+    KhrEglImage,
+    KhrEglEvent,
+    KhrDx9MediaSharing,
+    IntelDx9MediaSharing,
+    KhrD3d10Sharing,
+    KhrD3d11Sharing,
+    PoclContentSize,
+    ExtBufferDeviceAddress,
+    ImgCancelCommand,
+    QcomPerfHint,
+}
+
+impl fmt::Display for KnownExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl KnownExtension {
+    /// All the known extensions.
+    pub const ALL: [Self; 39] = [
+        Self::AppleSetMemObjectDestructor,
+        Self::AppleContextLoggingFunctions,
+        Self::KhrIcd,
+        Self::KhrIlProgram,
+        Self::KhrTerminateContext,
+        Self::KhrCreateCommandQueue,
+        Self::ExtDeviceFission,
+        Self::ExtMigrateMemobject,
+        Self::QcomExtHostPtr,
+        Self::ImgUseGrallocPtr,
+        Self::ImgGenerateMipmap,
+        Self::KhrSubgroups,
+        Self::KhrSuggestedLocalWorkSize,
+        Self::KhrExternalMemory,
+        Self::KhrExternalSemaphore,
+        Self::KhrExternalSemaphoreSyncFd,
+        Self::KhrSemaphore,
+        Self::ArmImportMemory,
+        Self::ArmSharedVirtualMemory,
+        Self::IntelAccelerator,
+        Self::IntelUnifiedSharedMemory,
+        Self::IntelCreateBufferWithProperties,
+        Self::IntelProgramScopeHostPipe,
+        Self::ExtImageRequirementsInfo,
+        Self::KhrCommandBuffer,
+        Self::KhrCommandBufferMultiDevice,
+        Self::KhrCommandBufferMutableDispatch,
+        Self::KhrGlSharing,
+        Self::KhrGlEvent,
+        Self::KhrEglImage,
+        Self::KhrEglEvent,
+        Self::KhrDx9MediaSharing,
+        Self::IntelDx9MediaSharing,
+        Self::KhrD3d10Sharing,
+        Self::KhrD3d11Sharing,
+        Self::PoclContentSize,
+        Self::ExtBufferDeviceAddress,
+        Self::ImgCancelCommand,
+        Self::QcomPerfHint,
+    ];
+
+    /// The name of the extension, as reported by [Device::extensions].
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::AppleSetMemObjectDestructor => "cl_apple_setmemobjectdestructor",
+            Self::AppleContextLoggingFunctions => "cl_apple_contextloggingfunctions",
+            Self::KhrIcd => "cl_khr_icd",
+            Self::KhrIlProgram => "cl_khr_il_program",
+            Self::KhrTerminateContext => "cl_khr_terminate_context",
+            Self::KhrCreateCommandQueue => "cl_khr_create_command_queue",
+            Self::ExtDeviceFission => "cl_ext_device_fission",
+            Self::ExtMigrateMemobject => "cl_ext_migrate_memobject",
+            Self::QcomExtHostPtr => "cl_qcom_ext_host_ptr",
+            Self::ImgUseGrallocPtr => "cl_img_use_gralloc_ptr",
+            Self::ImgGenerateMipmap => "cl_img_generate_mipmap",
+            Self::KhrSubgroups => "cl_khr_subgroups",
+            Self::KhrSuggestedLocalWorkSize => "cl_khr_suggested_local_work_size",
+            Self::KhrExternalMemory => "cl_khr_external_memory",
+            Self::KhrExternalSemaphore => "cl_khr_external_semaphore",
+            Self::KhrExternalSemaphoreSyncFd => "cl_khr_external_semaphore_sync_fd",
+            Self::KhrSemaphore => "cl_khr_semaphore",
+            Self::ArmImportMemory => "cl_arm_import_memory",
+            Self::ArmSharedVirtualMemory => "cl_arm_shared_virtual_memory",
+            Self::IntelAccelerator => "cl_intel_accelerator",
+            Self::IntelUnifiedSharedMemory => "cl_intel_unified_shared_memory",
+            Self::IntelCreateBufferWithProperties => "cl_intel_create_buffer_with_properties",
+            Self::IntelProgramScopeHostPipe => "cl_intel_program_scope_host_pipe",
+            Self::ExtImageRequirementsInfo => "cl_ext_image_requirements_info",
+            Self::KhrCommandBuffer => "cl_khr_command_buffer",
+            Self::KhrCommandBufferMultiDevice => "cl_khr_command_buffer_multi_device",
+            Self::KhrCommandBufferMutableDispatch => "cl_khr_command_buffer_mutable_dispatch",
+            Self::KhrGlSharing => "cl_khr_gl_sharing",
+            Self::KhrGlEvent => "cl_khr_gl_event",
+            Self::KhrEglImage => "cl_khr_egl_image",
+            Self::KhrEglEvent => "cl_khr_egl_event",
+            Self::KhrDx9MediaSharing => "cl_khr_dx9_media_sharing",
+            Self::IntelDx9MediaSharing => "cl_intel_dx9_media_sharing",
+            Self::KhrD3d10Sharing => "cl_khr_d3d10_sharing",
+            Self::KhrD3d11Sharing => "cl_khr_d3d11_sharing",
+            Self::PoclContentSize => "cl_pocl_content_size",
+            Self::ExtBufferDeviceAddress => "cl_ext_buffer_device_address",
+            Self::ImgCancelCommand => "cl_img_cancel_command",
+            Self::QcomPerfHint => "cl_qcom_perf_hint",
+        }
+    }
+
+    /// Find the known extension with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|ext| ext.name() == name)
+    }
+
+    /// Whether the cargo feature for the extension is enabled.  
+    /// Note: the `dynamic` feature enables the extension APIs without
+    /// enabling their features.
+    pub const fn is_feature_enabled(&self) -> bool {
+        match self {
+            Self::AppleSetMemObjectDestructor => cfg!(feature = "cl_apple_setmemobjectdestructor"),
+            Self::AppleContextLoggingFunctions => {
+                cfg!(feature = "cl_apple_contextloggingfunctions")
+            }
+            Self::KhrIcd => cfg!(feature = "cl_khr_icd"),
+            Self::KhrIlProgram => cfg!(feature = "cl_khr_il_program"),
+            Self::KhrTerminateContext => cfg!(feature = "cl_khr_terminate_context"),
+            Self::KhrCreateCommandQueue => cfg!(feature = "cl_khr_create_command_queue"),
+            Self::ExtDeviceFission => cfg!(feature = "cl_ext_device_fission"),
+            Self::ExtMigrateMemobject => cfg!(feature = "cl_ext_migrate_memobject"),
+            Self::QcomExtHostPtr => cfg!(feature = "cl_qcom_ext_host_ptr"),
+            Self::ImgUseGrallocPtr => cfg!(feature = "cl_img_use_gralloc_ptr"),
+            Self::ImgGenerateMipmap => cfg!(feature = "cl_img_generate_mipmap"),
+            Self::KhrSubgroups => cfg!(feature = "cl_khr_subgroups"),
+            Self::KhrSuggestedLocalWorkSize => cfg!(feature = "cl_khr_suggested_local_work_size"),
+            Self::KhrExternalMemory => cfg!(feature = "cl_khr_external_memory"),
+            Self::KhrExternalSemaphore => cfg!(feature = "cl_khr_external_semaphore"),
+            Self::KhrExternalSemaphoreSyncFd => cfg!(feature = "cl_khr_external_semaphore_sync_fd"),
+            Self::KhrSemaphore => cfg!(feature = "cl_khr_semaphore"),
+            Self::ArmImportMemory => cfg!(feature = "cl_arm_import_memory"),
+            Self::ArmSharedVirtualMemory => cfg!(feature = "cl_arm_shared_virtual_memory"),
+            Self::IntelAccelerator => cfg!(feature = "cl_intel_accelerator"),
+            Self::IntelUnifiedSharedMemory => cfg!(feature = "cl_intel_unified_shared_memory"),
+            Self::IntelCreateBufferWithProperties => {
+                cfg!(feature = "cl_intel_create_buffer_with_properties")
+            }
+            Self::IntelProgramScopeHostPipe => cfg!(feature = "cl_intel_program_scope_host_pipe"),
+            Self::ExtImageRequirementsInfo => cfg!(feature = "cl_ext_image_requirements_info"),
+            Self::KhrCommandBuffer => cfg!(feature = "cl_khr_command_buffer"),
+            Self::KhrCommandBufferMultiDevice => {
+                cfg!(feature = "cl_khr_command_buffer_multi_device")
+            }
+            Self::KhrCommandBufferMutableDispatch => {
+                cfg!(feature = "cl_khr_command_buffer_mutable_dispatch")
+            }
+            Self::KhrGlSharing => cfg!(feature = "cl_khr_gl_sharing"),
+            Self::KhrGlEvent => cfg!(feature = "cl_khr_gl_event"),
+            Self::KhrEglImage => cfg!(feature = "cl_khr_egl_image"),
+            Self::KhrEglEvent => cfg!(feature = "cl_khr_egl_event"),
+            Self::KhrDx9MediaSharing => cfg!(feature = "cl_khr_dx9_media_sharing"),
+            Self::IntelDx9MediaSharing => cfg!(feature = "cl_intel_dx9_media_sharing"),
+            Self::KhrD3d10Sharing => cfg!(feature = "cl_khr_d3d10_sharing"),
+            Self::KhrD3d11Sharing => cfg!(feature = "cl_khr_d3d11_sharing"),
+            Self::PoclContentSize => cfg!(feature = "cl_pocl_content_size"),
+            Self::ExtBufferDeviceAddress => cfg!(feature = "cl_ext_buffer_device_address"),
+            Self::ImgCancelCommand => cfg!(feature = "cl_img_cancel_command"),
+            Self::QcomPerfHint => cfg!(feature = "cl_qcom_perf_hint"),
+        }
+    }
+
+    /// The known extensions whose cargo features are enabled.
+    pub fn enabled_features() -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(Self::is_feature_enabled)
+            .collect()
+    }
+}
+
+/// A set of the OpenCL extensions supported by a [Device] or [Platform],
+/// with their versions where available.
+///
+/// Created from the space separated list of names returned by
+/// [Device::extensions] or [Platform::extensions], or from the names and
+/// versions returned by [Device::extensions_with_version] or
+/// [Platform::extensions_with_version].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extensions {
+    extensions: HashMap<String, Option<OpenClVersion>>,
+}
+
+impl Extensions {
+    /// Parse a space separated list of extension names.
+    pub fn parse(text: &str) -> Self {
+        Self {
+            extensions: text
+                .split_whitespace()
+                .map(|name| (name.to_owned(), None))
+                .collect(),
+        }
+    }
+
+    /// Create from an array of extension name and version structures.
+    pub fn from_name_versions(values: &[cl_name_version]) -> Self {
+        Self {
+            extensions: values
+                .iter()
+                .map(NameVersion::from)
+                .map(|value| (value.name, Some(OpenClVersion::from(value.version))))
+                .collect(),
+        }
+    }
+
+    /// The number of extensions in the set.
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Whether the set contains the named extension.
+    pub fn contains(&self, name: &str) -> bool {
+        self.extensions.contains_key(name)
+    }
+
+    /// Whether the set contains the known extension.
+    pub fn supports(&self, extension: KnownExtension) -> bool {
+        self.contains(extension.name())
+    }
+
+    /// The version of the named extension.  
+    /// Returns None if the extension is not in the set or if its version is
+    /// unknown, i.e. the set was parsed from a list of extension names.
+    pub fn version(&self, name: &str) -> Option<OpenClVersion> {
+        self.extensions.get(name).copied().flatten()
+    }
+
+    /// The known extensions in `required` that are not in the set.
+    pub fn missing(&self, required: &[KnownExtension]) -> Vec<KnownExtension> {
+        required
+            .iter()
+            .copied()
+            .filter(|extension| !self.supports(*extension))
+            .collect()
+    }
+
+    /// An iterator over the extension names and versions, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<OpenClVersion>)> {
+        self.extensions
+            .iter()
+            .map(|(name, version)| (name.as_str(), *version))
+    }
+}
+
+/// A [Device] chosen by a [DeviceSelector], together with its [Platform]
+/// and the score given to it by the selector's scoring function.
+#[derive(Copy, Clone, Debug)]
+pub struct SelectedDevice {
+    pub platform: Platform,
+    pub device: Device,
+    pub score: i64,
+}
+
+/// A function that scores a device for a [DeviceSelector], see
+/// [DeviceSelector::set_score].
+type ScoreFn = Box<dyn Fn(&Device) -> i64>;
+
+/// A struct that implements the [builder pattern](https://doc.rust-lang.org/1.0.0/style/ownership/builders.html).
+///
+/// To choose the most appropriate [Device]s from all the available [Platform]s.
+/// Devices that fail any of the filters are discarded and the remaining
+/// devices are ranked by the scoring function, highest score first.
+/// Devices with equal scores remain in platform and device enumeration order.
+///
+/// # Examples
+/// ```no_run
+/// use opencl3::device::{CL_DEVICE_SVM_COARSE_GRAIN_BUFFER, CL_DEVICE_TYPE_GPU, DeviceSelector};
+///
+/// let devices = DeviceSelector::new()
+///     .set_device_type(CL_DEVICE_TYPE_GPU)
+///     .set_min_version(2, 0)
+///     .set_svm_capabilities(CL_DEVICE_SVM_COARSE_GRAIN_BUFFER)
+///     .set_score(|device| i64::from(device.max_compute_units().unwrap_or_default()))
+///     .select()
+///     .unwrap();
+/// println!("Number of suitable devices: {}", devices.len());
+/// ```
+pub struct DeviceSelector {
+    pub device_type: DeviceType,
+    pub min_version: Option<(cl_uint, cl_uint)>,
+    pub extensions: Vec<String>,
+    pub min_global_mem_size: cl_ulong,
+    pub svm_capabilities: SvmCapabilities,
+    pub double_fp_config: FpConfig,
+    pub half_fp_config: FpConfig,
+
+    score: Option<ScoreFn>,
+}
+
+impl fmt::Debug for DeviceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceSelector")
+            .field("device_type", &self.device_type)
+            .field("min_version", &self.min_version)
+            .field("extensions", &self.extensions)
+            .field("min_global_mem_size", &self.min_global_mem_size)
+            .field("svm_capabilities", &self.svm_capabilities)
+            .field("double_fp_config", &self.double_fp_config)
+            .field("half_fp_config", &self.half_fp_config)
+            .field("score", &self.score.is_some())
+            .finish()
+    }
+}
+
+impl Default for DeviceSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceSelector {
+    /// Create a DeviceSelector that selects all devices of every type.
+    pub const fn new() -> Self {
+        Self {
+            device_type: DeviceType::ALL,
+            min_version: None,
+            extensions: Vec::new(),
+            min_global_mem_size: 0,
+            svm_capabilities: SvmCapabilities::empty(),
+            double_fp_config: FpConfig::empty(),
+            half_fp_config: FpConfig::empty(),
+
+            score: None,
+        }
+    }
+
+    /// Set the type of the devices to select, see
+    /// [Device Types](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#device-types-table).
+    ///
+    /// returns a reference to self.
+    pub fn set_device_type(&mut self, device_type: impl Into<DeviceType>) -> &mut Self {
+        self.device_type = device_type.into();
+        self
+    }
+
+    /// Set the minimum OpenCL version supported by the devices, i.e. the
+    /// version returned by [Device::version].
+    ///
+    /// returns a reference to self.
+    pub const fn set_min_version(&mut self, major: cl_uint, minor: cl_uint) -> &mut Self {
+        self.min_version = Some((major, minor));
+        self
+    }
+
+    /// Set an extension that the devices must support.
+    ///
+    /// * `name` - the name of the extension, e.g. "cl_khr_fp64".
+    ///
+    /// returns a reference to self.
+    pub fn set_extension(&mut self, name: &str) -> &mut Self {
+        self.extensions.push(name.to_owned());
+        self
+    }
+
+    /// Set the extensions that the devices must support.
+    ///
+    /// * `names` - the names of the extensions.
+    ///
+    /// returns a reference to self.
+    pub fn set_extensions(&mut self, names: &[&str]) -> &mut Self {
+        self.extensions
+            .extend(names.iter().map(|name| (*name).to_owned()));
+        self
+    }
+
+    /// Set the minimum size of global device memory in bytes.
+    ///
+    /// returns a reference to self.
+    pub const fn set_min_global_mem_size(&mut self, size: cl_ulong) -> &mut Self {
+        self.min_global_mem_size = size;
+        self
+    }
+
+    /// Set the Shared Virtual Memory (SVM) capabilities that the devices must
+    /// support, e.g. [SvmCapabilities::FINE_GRAIN_BUFFER].
+    ///
+    /// returns a reference to self.
+    pub fn set_svm_capabilities(
+        &mut self,
+        svm_capabilities: impl Into<SvmCapabilities>,
+    ) -> &mut Self {
+        self.svm_capabilities = svm_capabilities.into();
+        self
+    }
+
+    /// Set the double floating point capability that the devices must support,
+    /// see [Device::supports_double].
+    ///
+    /// returns a reference to self.
+    pub fn set_double_fp_config(&mut self, fp_config: impl Into<FpConfig>) -> &mut Self {
+        self.double_fp_config = fp_config.into();
+        self
+    }
+
+    /// Set the half floating point capability that the devices must support,
+    /// see [Device::supports_half].
+    ///
+    /// returns a reference to self.
+    pub fn set_half_fp_config(&mut self, fp_config: impl Into<FpConfig>) -> &mut Self {
+        self.half_fp_config = fp_config.into();
+        self
+    }
+
+    /// Set the function used to rank the devices that pass the filters,
+    /// the device with the highest score is first.
+    ///
+    /// returns a reference to self.
+    pub fn set_score<F>(&mut self, score: F) -> &mut Self
+    where
+        F: Fn(&Device) -> i64 + 'static,
+    {
+        self.score = Some(Box::new(score));
+        self
+    }
+
+    /// Determine whether a device passes all of the filters.
+    pub fn is_suitable(&self, device: &Device) -> bool {
+        if self
+            .min_version
+            .is_some_and(|(major, minor)| !device.supports_version(major, minor))
+        {
+            return false;
+        }
+
+        if !self.extensions.is_empty() {
+            let Ok(extensions) = device.extension_set() else {
+                return false;
+            };
+            if !self.extensions.iter().all(|ext| extensions.contains(ext)) {
+                return false;
+            }
+        }
+
+        if 0 < self.min_global_mem_size
+            && !device
+                .global_mem_size()
+                .is_ok_and(|size| self.min_global_mem_size <= size)
+        {
+            return false;
+        }
+
+        if !SvmCapabilities::from(device.svm_mem_capability()).contains(self.svm_capabilities) {
+            return false;
+        }
+
+        if !self.double_fp_config.is_empty()
+            && !device.supports_double(self.double_fp_config.bits())
+        {
+            return false;
+        }
+
+        self.half_fp_config.is_empty() || device.supports_half(self.half_fp_config.bits())
+    }
+
+    /// Find the devices of the required type on all the available platforms
+    /// that pass the filters and rank them with the scoring function.
+    ///
+    /// returns a Result containing the suitable devices, highest score first,
+    /// or the error code from the OpenCL C API function.
+    pub fn select(&self) -> Result<Vec<SelectedDevice>> {
+        let mut selected = Vec::<SelectedDevice>::new();
+
+        for platform in get_platforms()? {
+            for device_id in platform.get_devices(self.device_type.bits())? {
+                let device = Device::new(device_id);
+                if self.is_suitable(&device) {
+                    let score = self.score.as_ref().map_or(0, |score| score(&device));
+                    selected.push(SelectedDevice {
+                        platform,
+                        device,
+                        score,
+                    });
+                }
+            }
+        }
+
+        selected.sort_by_key(|selected| Reverse(selected.score));
+        Ok(selected)
+    }
+}
+
+/// A way to partition an OpenCL device into sub-devices, see
+/// [Subdevice Partition](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#subdevice-partition-table).
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Partition<'a> {
+    /// Split into as many sub-devices as possible, each containing the
+    /// given number of compute units.
+    Equally(cl_uint),
+    /// Split into a sub-device for each count, containing that number of
+    /// compute units.
+    ByCounts(&'a [cl_uint]),
+    /// Split along the given affinity domain, e.g. CL_DEVICE_AFFINITY_DOMAIN_NUMA.
+    ByAffinityDomain(cl_device_affinity_domain),
+}
+
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+impl Partition<'_> {
+    /// The partition property name, e.g. CL_DEVICE_PARTITION_EQUALLY.
+    pub const fn name(&self) -> cl_device_partition_property {
+        match self {
+            Self::Equally(_) => CL_DEVICE_PARTITION_EQUALLY,
+            Self::ByCounts(_) => CL_DEVICE_PARTITION_BY_COUNTS,
+            Self::ByAffinityDomain(_) => CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN,
+        }
+    }
+
+    /// The zero terminated list of partition properties for
+    /// clCreateSubDevices.
+    pub fn properties(&self) -> Vec<cl_device_partition_property> {
+        let mut properties = vec![self.name()];
+        match self {
+            Self::Equally(count) => properties.push(*count as cl_device_partition_property),
+            Self::ByCounts(counts) => {
+                properties.extend(
+                    counts
+                        .iter()
+                        .map(|count| *count as cl_device_partition_property),
+                );
+                properties.push(CL_DEVICE_PARTITION_BY_COUNTS_LIST_END);
+            }
+            Self::ByAffinityDomain(domain) => {
+                properties.push(*domain as cl_device_partition_property);
+            }
+        }
+        properties.push(0);
+        properties
+    }
+}
+
+/// Check the counts of a [Partition::ByCounts] against the maximum number
+/// of sub-devices and compute units of a device.  
+/// A zero count is invalid, since it would terminate the list of counts.
+///
+/// returns an empty Result or CL_INVALID_DEVICE_PARTITION_COUNT.
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+fn check_partition_counts(
+    counts: &[cl_uint],
+    max_sub_devices: usize,
+    max_compute_units: cl_uint,
+) -> Result<()> {
+    let total = counts
+        .iter()
+        .try_fold(0u32, |total, &count| total.checked_add(count));
+    let valid = !counts.is_empty()
+        && counts.len() <= max_sub_devices
+        && !counts.contains(&0)
+        && total.is_some_and(|total| total <= max_compute_units);
+    if valid {
+        Ok(())
+    } else {
+        Err(ClError(CL_INVALID_DEVICE_PARTITION_COUNT))
+    }
+}
+
+/// An OpenCL sub-device, created by partitioning a [Device].
+///
+/// Dereferences to a [Device], so it has all the [Device] query methods
+/// and can be used wherever a `&Device` is expected, e.g.
+/// [Context::from_device](crate::context::Context::from_device).  
+/// Implements the Drop trait to call release_device when the object is dropped.
 #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
 #[derive(Debug)]
 pub struct SubDevice {
-    id: cl_device_id,
+    device: Device,
 }
 
 #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
 impl From<cl_device_id> for SubDevice {
     fn from(id: cl_device_id) -> Self {
-        Self { id }
+        Self::new(id)
     }
 }
 
 #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
 impl From<SubDevice> for cl_device_id {
     fn from(value: SubDevice) -> Self {
-        value.id
+        value.id()
+    }
+}
+
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+impl Deref for SubDevice {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        &self.device
     }
 }
 
 #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
 impl Drop for SubDevice {
     fn drop(&mut self) {
-        unsafe { release_device(self.id()).expect("Error: clReleaseDevice") };
+        check_release("clReleaseDevice", unsafe { release_device(self.id()) });
+    }
+}
+
+#[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+impl Clone for SubDevice {
+    /// Retain the OpenCL sub-device, so that both SubDevices share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.id()) }.expect("Error: clRetainDevice")
     }
 }
 
@@ -71,12 +832,37 @@ unsafe impl Sync for SubDevice {}
 #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
 impl SubDevice {
     pub const fn new(id: cl_device_id) -> Self {
-        Self { id }
+        Self {
+            device: Device::new(id),
+        }
+    }
+
+    /// Create a SubDevice from an OpenCL sub-device id that is owned elsewhere.  
+    /// Calls clRetainDevice, so that the SubDevice can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `id` must be a valid OpenCL sub-device id.
+    pub unsafe fn from_raw_retained(id: cl_device_id) -> Result<Self> {
+        unsafe { retain_device(id)? };
+        Ok(Self::new(id))
     }
 
     /// Accessor for the underlying device id.
     pub const fn id(&self) -> cl_device_id {
-        self.id
+        self.device.id()
+    }
+
+    /// Accessor for the sub-device as a [Device].
+    pub const fn device(&self) -> Device {
+        self.device
+    }
+
+    /// Release the OpenCL sub-device, returning any error from clReleaseDevice instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { release_device(this.id()) }?)
     }
 }
 
@@ -86,20 +872,18 @@ impl SubDevice {
 /// [Device Queries](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#device-queries-table).
 #[derive(Copy, Clone, Debug)]
 pub struct Device {
-    id: intptr_t,
+    id: cl_device_id,
 }
 
 impl From<cl_device_id> for Device {
     fn from(value: cl_device_id) -> Self {
-        Self {
-            id: value as intptr_t,
-        }
+        Self { id: value }
     }
 }
 
 impl From<Device> for cl_device_id {
     fn from(value: Device) -> Self {
-        value.id as Self
+        value.id
     }
 }
 
@@ -107,13 +891,13 @@ unsafe impl Send for Device {}
 unsafe impl Sync for Device {}
 
 impl Device {
-    pub fn new(id: cl_device_id) -> Self {
-        Self { id: id as intptr_t }
+    pub const fn new(id: cl_device_id) -> Self {
+        Self { id }
     }
 
     /// Accessor for the underlying device id.
     pub const fn id(&self) -> cl_device_id {
-        self.id as cl_device_id
+        self.id
     }
 
     /// Create sub-devices by partitioning an OpenCL device.
@@ -135,6 +919,51 @@ impl Device {
             .collect::<Vec<SubDevice>>())
     }
 
+    /// Create sub-devices by partitioning an OpenCL device.
+    ///
+    /// Validates the partition against the partition types, maximum number
+    /// of sub-devices, compute units and affinity domains that the device
+    /// supports before calling clCreateSubDevices.
+    ///
+    /// * `partition` - how to partition the device.
+    ///
+    /// returns a Result containing a vector of the SubDevices, or
+    /// CL_INVALID_VALUE if the device does not support the partition,
+    /// CL_INVALID_DEVICE_PARTITION_COUNT if the counts are invalid,
+    /// or the error code from the OpenCL C API function.
+    #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+    pub fn partition(&self, partition: &Partition) -> Result<Vec<SubDevice>> {
+        let supported = self.partition_properties()?;
+        if !supported.contains(&partition.name()) {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+
+        let max_compute_units = self.max_compute_units()?;
+        match partition {
+            Partition::Equally(count) => {
+                if 0 == *count || max_compute_units < *count {
+                    return Err(ClError(CL_INVALID_VALUE));
+                }
+            }
+            Partition::ByCounts(counts) => {
+                let max_sub_devices = self.partition_max_sub_devices()? as usize;
+                check_partition_counts(counts, max_sub_devices, max_compute_units)?;
+            }
+            Partition::ByAffinityDomain(domain) => {
+                let domains = self
+                    .partition_affinity_domain()?
+                    .first()
+                    .copied()
+                    .unwrap_or_default();
+                if 1 != domain.count_ones() || 0 == domains & domain {
+                    return Err(ClError(CL_INVALID_VALUE));
+                }
+            }
+        }
+
+        self.create_sub_devices(&partition.properties())
+    }
+
     #[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
     #[inline]
     pub fn get_device_and_host_timer(&self) -> Result<[cl_ulong; 2]> {
@@ -769,6 +1598,38 @@ impl Device {
         Ok(get_device_pci_bus_info_khr(&value))
     }
 
+    /// The PCI bus location of the device from the first of the
+    /// cl_khr_pci_bus_info, AMD topology or Nvidia PCI queries that the
+    /// device supports.  
+    /// The Nvidia queries do not provide the PCI domain or function,
+    /// so they are set to zero.
+    pub fn pci_bus_info(&self) -> Option<PciBusInfo> {
+        self.pcibusinfokhr_intel()
+            .map(PciBusInfo::from)
+            .or_else(|_| self.topology_amd().map(PciBusInfo::from))
+            .or_else(|_| {
+                Ok::<_, ClError>(PciBusInfo {
+                    domain: 0,
+                    bus: self.pci_bus_id_nv()?,
+                    device: self.pci_slot_id_nv()?,
+                    function: 0,
+                })
+            })
+            .ok()
+    }
+
+    /// The stable identity of the device, see [DeviceIdentity].
+    ///
+    /// returns a Result containing the DeviceIdentity
+    /// or the error code from the OpenCL C API function.
+    pub fn identity(&self) -> Result<DeviceIdentity> {
+        device_identities()?
+            .into_iter()
+            .find(|(device, _)| device.id() == self.id())
+            .map(|(_, identity)| identity)
+            .ok_or(ClError(CL_DEVICE_NOT_FOUND))
+    }
+
     pub fn pci_bus_id_intel(&self) -> Result<cl_uint> {
         let value = self.pcibusinfokhr_intel()?;
         Ok(value.pci_bus as cl_uint)
@@ -914,19 +1775,65 @@ impl Device {
         Ok(get_device_data(self.id(), param_name)?)
     }
 
+    /// Get the OpenCL version supported by the device, parsed from [Device::version].
+    ///
+    /// returns a Result containing the version, or CL_INVALID_VALUE if the
+    /// version string could not be parsed, or the error code from the OpenCL
+    /// C API function.
+    pub fn opencl_version(&self) -> Result<OpenClVersion> {
+        OpenClVersion::parse(&self.version()?).ok_or(ClError(CL_INVALID_VALUE))
+    }
+
+    /// Get the highest OpenCL C version supported by the device compiler,
+    /// parsed from [Device::opencl_c_version].
+    ///
+    /// returns a Result containing the version, or CL_INVALID_VALUE if the
+    /// version string could not be parsed, or the error code from the OpenCL
+    /// C API function.
+    pub fn opencl_c_numeric_version(&self) -> Result<OpenClVersion> {
+        OpenClVersion::parse(&self.opencl_c_version()?).ok_or(ClError(CL_INVALID_VALUE))
+    }
+
+    /// Get the set of extensions supported by the device.  
+    /// Uses [Device::extensions_with_version] if the device supports it
+    /// (CL_VERSION_3_0), otherwise parses [Device::extensions].
+    ///
+    /// returns a Result containing the Extensions
+    /// or the error code from the OpenCL C API function.
+    pub fn extension_set(&self) -> Result<Extensions> {
+        self.extensions_with_version().map_or_else(
+            |_| Ok(Extensions::parse(&self.extensions()?)),
+            |values| Ok(Extensions::from_name_versions(&values)),
+        )
+    }
+
+    /// Determine if the device supports OpenCL version major.minor or later.  
+    /// Returns true if the device supports it, false otherwise.
+    ///
+    /// Used to decide at runtime which OpenCL API functions may be called
+    /// on the device, e.g.:
+    /// [CommandQueue::create_with_properties](crate::command_queue::CommandQueue::create_with_properties)
+    /// requires OpenCL 2.0.
+    pub fn supports_version(&self, major: cl_uint, minor: cl_uint) -> bool {
+        self.opencl_version()
+            .is_ok_and(|version| version.is_at_least(major, minor))
+    }
+
     /// Determine if the device supports the given half floating point capability.  
     /// Returns true if the device supports it, false otherwise.
-    pub fn supports_half(&self, min_fp_capability: cl_device_fp_config) -> bool {
+    pub fn supports_half(&self, min_fp_capability: impl Into<FpConfig>) -> bool {
+        let min_fp_capability = min_fp_capability.into();
         self.half_fp_config()
-            .is_ok_and(|fp| 0 < fp & min_fp_capability)
+            .is_ok_and(|fp| FpConfig::from(fp).intersects(min_fp_capability))
     }
     /// Determine if the device supports the given double floating point capability.  
     /// Returns true if the device supports it, false otherwise.
     ///
     /// CL_VERSION_1_2
-    pub fn supports_double(&self, min_fp_capability: cl_device_fp_config) -> bool {
+    pub fn supports_double(&self, min_fp_capability: impl Into<FpConfig>) -> bool {
+        let min_fp_capability = min_fp_capability.into();
         self.double_fp_config()
-            .is_ok_and(|fp| 0 < fp & min_fp_capability)
+            .is_ok_and(|fp| FpConfig::from(fp).intersects(min_fp_capability))
     }
 
     /// Determine if the device supports SVM and, if so, what kind of SVM.  
@@ -937,6 +1844,197 @@ impl Device {
         self.svm_capabilities().map_or(0, |svm| svm)
     }
 
+    /// Query all the standard and vendor specific information about the device.
+    /// Queries that the device does not support are set to None.
+    ///
+    /// returns a [DeviceInfo] snapshot of the device.
+    pub fn info(&self) -> DeviceInfo {
+        DeviceInfo {
+            dev_type: self.dev_type().ok(),
+            vendor_id: self.vendor_id().ok(),
+            max_compute_units: self.max_compute_units().ok(),
+            max_work_item_dimensions: self.max_work_item_dimensions().ok(),
+            max_work_group_size: self.max_work_group_size().ok(),
+            max_work_item_sizes: self.max_work_item_sizes().ok(),
+            max_preferred_vector_width_char: self.max_preferred_vector_width_char().ok(),
+            max_preferred_vector_width_short: self.max_preferred_vector_width_short().ok(),
+            max_preferred_vector_width_int: self.max_preferred_vector_width_int().ok(),
+            max_preferred_vector_width_long: self.max_preferred_vector_width_long().ok(),
+            max_preferred_vector_width_float: self.max_preferred_vector_width_float().ok(),
+            max_preferred_vector_width_double: self.max_preferred_vector_width_double().ok(),
+            max_clock_frequency: self.max_clock_frequency().ok(),
+            address_bits: self.address_bits().ok(),
+            max_read_image_args: self.max_read_image_args().ok(),
+            max_write_image_args: self.max_write_image_args().ok(),
+            max_mem_alloc_size: self.max_mem_alloc_size().ok(),
+            image2d_max_width: self.image2d_max_width().ok(),
+            image2d_max_height: self.image2d_max_height().ok(),
+            image3d_max_width: self.image3d_max_width().ok(),
+            image3d_max_height: self.image3d_max_height().ok(),
+            image3d_max_depth: self.image3d_max_depth().ok(),
+            image_support: self.image_support().ok(),
+            max_parameter_size: self.max_parameter_size().ok(),
+            max_device_samples: self.max_device_samples().ok(),
+            mem_base_addr_align: self.mem_base_addr_align().ok(),
+            min_data_type_align_size: self.min_data_type_align_size().ok(),
+            single_fp_config: self.single_fp_config().ok(),
+            global_mem_cache_type: self.global_mem_cache_type().ok(),
+            global_mem_cacheline_size: self.global_mem_cacheline_size().ok(),
+            global_mem_cache_size: self.global_mem_cache_size().ok(),
+            global_mem_size: self.global_mem_size().ok(),
+            max_constant_buffer_size: self.max_constant_buffer_size().ok(),
+            max_constant_args: self.max_constant_args().ok(),
+            local_mem_type: self.local_mem_type().ok(),
+            local_mem_size: self.local_mem_size().ok(),
+            error_correction_support: self.error_correction_support().ok(),
+            profiling_timer_resolution: self.profiling_timer_resolution().ok(),
+            endian_little: self.endian_little().ok(),
+            available: self.available().ok(),
+            compiler_available: self.compiler_available().ok(),
+            execution_capabilities: self.execution_capabilities().ok(),
+            queue_on_host_properties: self.queue_on_host_properties().ok(),
+            name: self.name().ok(),
+            vendor: self.vendor().ok(),
+            driver_version: self.driver_version().ok(),
+            profile: self.profile().ok(),
+            version: self.version().ok(),
+            extensions: self.extensions().ok(),
+            double_fp_config: self.double_fp_config().ok(),
+            half_fp_config: self.half_fp_config().ok(),
+            preferred_vector_width_half: self.preferred_vector_width_half().ok(),
+            host_unified_memory: self.host_unified_memory().ok(),
+            native_vector_width_char: self.native_vector_width_char().ok(),
+            native_vector_width_short: self.native_vector_width_short().ok(),
+            native_vector_width_int: self.native_vector_width_int().ok(),
+            native_vector_width_long: self.native_vector_width_long().ok(),
+            native_vector_width_float: self.native_vector_width_float().ok(),
+            native_vector_width_double: self.native_vector_width_double().ok(),
+            native_vector_width_half: self.native_vector_width_half().ok(),
+            opencl_c_version: self.opencl_c_version().ok(),
+            linker_available: self.linker_available().ok(),
+            built_in_kernels: self.built_in_kernels().ok(),
+            image_max_buffer_size: self.image_max_buffer_size().ok(),
+            image_max_array_size: self.image_max_array_size().ok(),
+            partition_max_sub_devices: self.partition_max_sub_devices().ok(),
+            partition_properties: self.partition_properties().ok(),
+            partition_affinity_domain: self.partition_affinity_domain().ok(),
+            partition_type: self.partition_type().ok(),
+            preferred_interop_user_sync: self.preferred_interop_user_sync().ok(),
+            printf_buffer_size: self.printf_buffer_size().ok(),
+            image_pitch_alignment: self.image_pitch_alignment().ok(),
+            image_base_address_alignment: self.image_base_address_alignment().ok(),
+            max_read_write_image_args: self.max_read_write_image_args().ok(),
+            max_global_variable_size: self.max_global_variable_size().ok(),
+            queue_on_device_properties: self.queue_on_device_properties().ok(),
+            queue_on_device_preferred_size: self.queue_on_device_preferred_size().ok(),
+            queue_on_device_max_size: self.queue_on_device_max_size().ok(),
+            max_on_device_queues: self.max_on_device_queues().ok(),
+            max_on_device_events: self.max_on_device_events().ok(),
+            svm_capabilities: self.svm_capabilities().ok(),
+            global_variable_preferred_total_size: self.global_variable_preferred_total_size().ok(),
+            max_pipe_args: self.max_pipe_args().ok(),
+            pipe_max_active_reservations: self.pipe_max_active_reservations().ok(),
+            pipe_max_packet_size: self.pipe_max_packet_size().ok(),
+            preferred_platform_atomic_alignment: self.preferred_platform_atomic_alignment().ok(),
+            preferred_global_atomic_alignment: self.preferred_global_atomic_alignment().ok(),
+            preferred_local_atomic_alignment: self.preferred_local_atomic_alignment().ok(),
+            il_version: self.il_version().ok(),
+            max_num_sub_groups: self.max_num_sub_groups().ok(),
+            sub_group_independent_forward_progress: self
+                .sub_group_independent_forward_progress()
+                .ok(),
+            numeric_version: self.numeric_version().ok(),
+            extensions_with_version: name_versions(self.extensions_with_version()),
+            ils_with_version: name_versions(self.ils_with_version()),
+            built_in_kernels_with_version: name_versions(self.built_in_kernels_with_version()),
+            atomic_memory_capabilities: self.atomic_memory_capabilities().ok(),
+            atomic_fence_capabilities: self.atomic_fence_capabilities().ok(),
+            non_uniform_work_group_support: self.non_uniform_work_group_support().ok(),
+            opencl_c_all_versions: name_versions(self.opencl_c_all_versions()),
+            preferred_work_group_size_multiple: self.preferred_work_group_size_multiple().ok(),
+            work_group_collective_functions_support: self
+                .work_group_collective_functions_support()
+                .ok(),
+            generic_address_space_support: self.generic_address_space_support().ok(),
+            uuid_khr: self.uuid_khr().ok(),
+            driver_uuid_khr: self.driver_uuid_khr().ok(),
+            luid_valid_khr: self.luid_valid_khr().ok(),
+            luid_khr: self.luid_khr().ok(),
+            node_mask_khr: self.node_mask_khr().ok(),
+            opencl_c_features: name_versions(self.opencl_c_features()),
+            device_enqueue_capabilities: self.device_enqueue_capabilities().ok(),
+            pipe_support: self.pipe_support().ok(),
+            latest_conformance_version_passed: self.latest_conformance_version_passed().ok(),
+            integer_dot_product_capabilities_khr: self.integer_dot_product_capabilities_khr().ok(),
+            integer_dot_product_acceleration_properties_8bit_khr: self
+                .integer_dot_product_acceleration_properties_8bit_khr()
+                .ok()
+                .map(IntegerDotProductAccelerationProperties::from),
+            integer_dot_product_acceleration_properties_4x8bit_packed_khr: self
+                .integer_dot_product_acceleration_properties_4x8bit_packed_khr()
+                .ok()
+                .map(IntegerDotProductAccelerationProperties::from),
+            compute_capability_major_nv: self.compute_capability_major_nv().ok(),
+            compute_capability_minor_nv: self.compute_capability_minor_nv().ok(),
+            registers_per_block_nv: self.registers_per_block_nv().ok(),
+            wrap_size_nv: self.wrap_size_nv().ok(),
+            gpu_overlap_nv: self.gpu_overlap_nv().ok(),
+            compute_kernel_exec_timeout_nv: self.compute_kernel_exec_timeout_nv().ok(),
+            integrated_memory_nv: self.integrated_memory_nv().ok(),
+            pci_bus_id_nv: self.pci_bus_id_nv().ok(),
+            pci_slot_id_nv: self.pci_slot_id_nv().ok(),
+            profiling_timer_offset_amd: self.profiling_timer_offset_amd().ok(),
+            topology_amd: self.topology_amd().ok().map(PciBusInfo::from),
+            pci_bus_id_amd: self.pci_bus_id_amd().ok(),
+            pcibusinfokhr_intel: self.pcibusinfokhr_intel().ok().map(PciBusInfo::from),
+            pci_bus_id_intel: self.pci_bus_id_intel().ok(),
+            board_name_amd: self.board_name_amd().ok(),
+            global_free_memory_amd: self.global_free_memory_amd().ok(),
+            simd_per_compute_unit_amd: self.simd_per_compute_unit_amd().ok(),
+            simd_width_amd: self.simd_width_amd().ok(),
+            simd_instruction_width_amd: self.simd_instruction_width_amd().ok(),
+            wavefront_width_amd: self.wavefront_width_amd().ok(),
+            global_mem_channels_amd: self.global_mem_channels_amd().ok(),
+            global_mem_channel_banks_amd: self.global_mem_channel_banks_amd().ok(),
+            global_mem_channel_bank_width_amd: self.global_mem_channel_bank_width_amd().ok(),
+            local_mem_size_per_compute_unit_amd: self.local_mem_size_per_compute_unit_amd().ok(),
+            local_mem_banks_amd: self.local_mem_banks_amd().ok(),
+            thread_trace_supported_amd: self.thread_trace_supported_amd().ok(),
+            gfxip_major_amd: self.gfxip_major_amd().ok(),
+            gfxip_minor_amd: self.gfxip_minor_amd().ok(),
+            available_async_queues_amd: self.available_async_queues_amd().ok(),
+            preferred_work_group_size_amd: self.preferred_work_group_size_amd().ok(),
+            max_work_group_size_amd: self.max_work_group_size_amd().ok(),
+            preferred_constant_buffer_size_amd: self.preferred_constant_buffer_size_amd().ok(),
+            pcie_id_amd: self.pcie_id_amd().ok(),
+            device_ip_version_intel: self.device_ip_version_intel().ok(),
+            device_id_intel: self.device_id_intel().ok(),
+            device_num_slices_intel: self.device_num_slices_intel().ok(),
+            device_num_sub_slices_per_slice_intel: self
+                .device_num_sub_slices_per_slice_intel()
+                .ok(),
+            device_num_eus_per_sub_slice_intel: self.device_num_eus_per_sub_slice_intel().ok(),
+            device_num_threads_per_eu_intel: self.device_num_threads_per_eu_intel().ok(),
+            device_feature_capabilities_intel: self.device_feature_capabilities_intel().ok(),
+            device_external_memory_import_handle_types_khr: self
+                .device_external_memory_import_handle_types_khr()
+                .ok(),
+            device_semaphore_import_handle_types_khr: self
+                .device_semaphore_import_handle_types_khr()
+                .ok(),
+            device_semaphore_export_handle_types_khr: self
+                .device_semaphore_export_handle_types_khr()
+                .ok(),
+            device_semaphore_types_khr: self.device_semaphore_types_khr().ok(),
+            device_command_buffer_capabilities_khr: self
+                .device_command_buffer_capabilities_khr()
+                .ok(),
+            device_command_buffer_required_queue_properties_khr: self
+                .device_command_buffer_required_queue_properties_khr()
+                .ok(),
+        }
+    }
+
     #[cfg(any(feature = "cl_khr_external_semaphore", feature = "dynamic"))]
     pub fn get_semaphore_handle_for_type_khr(
         &self,
@@ -951,6 +2049,500 @@ impl Device {
     }
 }
 
+/// The name and version of an OpenCL extension, IL, built-in kernel,
+/// OpenCL C version or feature, see: [cl_name_version].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NameVersion {
+    pub name: String,
+    pub version: cl_version,
+}
+
+impl From<&cl_name_version> for NameVersion {
+    fn from(value: &cl_name_version) -> Self {
+        let name: Vec<u8> = value
+            .name
+            .iter()
+            .take_while(|c| 0 != **c)
+            .copied()
+            .collect();
+        Self {
+            name: String::from_utf8_lossy(&name).into_owned(),
+            version: value.version,
+        }
+    }
+}
+
+pub(crate) fn name_versions(values: Result<Vec<cl_name_version>>) -> Option<Vec<NameVersion>> {
+    values
+        .ok()
+        .map(|values| values.iter().map(NameVersion::from).collect())
+}
+
+/// The PCI bus location of a device, see: [cl_device_pci_bus_info_khr].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PciBusInfo {
+    pub domain: cl_uint,
+    pub bus: cl_uint,
+    pub device: cl_uint,
+    pub function: cl_uint,
+}
+
+impl From<cl_device_pci_bus_info_khr> for PciBusInfo {
+    fn from(value: cl_device_pci_bus_info_khr) -> Self {
+        Self {
+            domain: value.pci_domain,
+            bus: value.pci_bus,
+            device: value.pci_device,
+            function: value.pci_function,
+        }
+    }
+}
+
+/// The PCI bus location of an AMD device, see: [cl_amd_device_topology].  
+/// The AMD topology does not provide the PCI domain, so it is set to zero.
+impl From<cl_amd_device_topology> for PciBusInfo {
+    fn from(value: cl_amd_device_topology) -> Self {
+        Self {
+            domain: 0,
+            bus: cl_uint::from(value.bus),
+            device: cl_uint::from(value.device),
+            function: cl_uint::from(value.function),
+        }
+    }
+}
+
+/// Whether the integer dot product operations of a device are accelerated,
+/// see: [cl_device_integer_dot_product_acceleration_properties_khr].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntegerDotProductAccelerationProperties {
+    pub signed_accelerated: bool,
+    pub unsigned_accelerated: bool,
+    pub mixed_signedness_accelerated: bool,
+    pub accumulating_saturating_signed_accelerated: bool,
+    pub accumulating_saturating_unsigned_accelerated: bool,
+    pub accumulating_saturating_mixed_signedness_accelerated: bool,
+}
+
+impl From<cl_device_integer_dot_product_acceleration_properties_khr>
+    for IntegerDotProductAccelerationProperties
+{
+    fn from(value: cl_device_integer_dot_product_acceleration_properties_khr) -> Self {
+        Self {
+            signed_accelerated: 0 != value.signed_accelerated,
+            unsigned_accelerated: 0 != value.unsigned_accelerated,
+            mixed_signedness_accelerated: 0 != value.mixed_signedness_accelerated,
+            accumulating_saturating_signed_accelerated: 0
+                != value.accumulating_saturating_signed_accelerated,
+            accumulating_saturating_unsigned_accelerated: 0
+                != value.accumulating_saturating_unsigned_accelerated,
+            accumulating_saturating_mixed_signedness_accelerated: 0
+                != value.accumulating_saturating_mixed_signedness_accelerated,
+        }
+    }
+}
+
+/// An identity for an OpenCL device that is stable across runs of an
+/// application, unlike a `cl_device_id`.
+///
+/// It is built from the device's UUID, LUID and PCI bus location where the
+/// device supports them, together with its platform, vendor and name and its
+/// index among the devices with the same platform, vendor and name.  
+/// Devices are matched by the strongest identifier that both identities
+/// have: UUID, then LUID, then PCI bus location, falling back to platform,
+/// vendor, name and index.
+/// The driver UUID is recorded but not matched, since it changes when the
+/// driver is updated.
+///
+/// A DeviceIdentity can be converted to and from a string, e.g. to store
+/// an application user's device choice, and resolved back to a [Device] by
+/// [DeviceIdentity::resolve].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceIdentity {
+    pub uuid: Option<[u8; CL_UUID_SIZE_KHR]>,
+    pub driver_uuid: Option<[u8; CL_UUID_SIZE_KHR]>,
+    pub luid: Option<[u8; CL_LUID_SIZE_KHR]>,
+    pub pci: Option<PciBusInfo>,
+    pub platform: String,
+    pub vendor: String,
+    pub name: String,
+    pub index: usize,
+}
+
+/// Get the devices of all types on all platforms with their identities.
+pub fn device_identities() -> Result<Vec<(Device, DeviceIdentity)>> {
+    let mut identities = Vec::<(Device, DeviceIdentity)>::new();
+    for id in get_all_devices(CL_DEVICE_TYPE_ALL)? {
+        let device = Device::new(id);
+        let mut identity = DeviceIdentity::from_device(&device)?;
+        identity.index = identities
+            .iter()
+            .filter(|(_, other)| {
+                other.platform == identity.platform
+                    && other.vendor == identity.vendor
+                    && other.name == identity.name
+            })
+            .count();
+        identities.push((device, identity));
+    }
+    Ok(identities)
+}
+
+/// None if the identifier is all zeros, as returned by some drivers.
+fn non_zero<const N: usize>(value: Result<[u8; N]>) -> Option<[u8; N]> {
+    value
+        .ok()
+        .filter(|value| value.iter().any(|byte| 0 != *byte))
+}
+
+impl DeviceIdentity {
+    /// The identity of a device, with an index of zero.
+    fn from_device(device: &Device) -> Result<Self> {
+        let luid_valid = device.luid_valid_khr().unwrap_or_default();
+        Ok(Self {
+            uuid: non_zero(device.uuid_khr()),
+            driver_uuid: non_zero(device.driver_uuid_khr()),
+            luid: if luid_valid {
+                non_zero(device.luid_khr())
+            } else {
+                None
+            },
+            pci: device.pci_bus_info(),
+            platform: Platform::new(device.platform()?).name()?,
+            vendor: device.vendor()?,
+            name: device.name()?,
+            index: 0,
+        })
+    }
+
+    /// Whether this identity and another identify the same device, compared
+    /// by the strongest identifier that both have.
+    pub fn matches(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.uuid, other.uuid) {
+            a == b
+        } else if let (Some(a), Some(b)) = (self.luid, other.luid) {
+            a == b
+        } else if let (Some(a), Some(b)) = (self.pci, other.pci) {
+            a == b
+        } else {
+            self.platform == other.platform
+                && self.vendor == other.vendor
+                && self.name == other.name
+                && self.index == other.index
+        }
+    }
+
+    /// Find the device with this identity on the host system.
+    ///
+    /// returns a Result containing the Device
+    /// or CL_DEVICE_NOT_FOUND if no device matches this identity.
+    pub fn resolve(&self) -> Result<Device> {
+        device_identities()?
+            .into_iter()
+            .find(|(_, identity)| self.matches(identity))
+            .map(|(device, _)| device)
+            .ok_or(ClError(CL_DEVICE_NOT_FOUND))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex<const N: usize>(text: &str) -> Option<[u8; N]> {
+    if text.len() != 2 * N || !text.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Escape the separators in a DeviceIdentity string value.
+fn escape(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace(';', "%3B")
+        .replace('=', "%3D")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("%3D", "=")
+        .replace("%3B", ";")
+        .replace("%25", "%")
+}
+
+/// Formats as `key=value` pairs separated by `;`, omitting the identifiers
+/// that the device does not support, e.g.:
+/// `uuid=<hex>;pci=0000:01:00.0;platform=<name>;vendor=<name>;name=<name>;index=0`
+impl fmt::Display for DeviceIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(uuid) = &self.uuid {
+            write!(f, "uuid={};", to_hex(uuid))?;
+        }
+        if let Some(driver_uuid) = &self.driver_uuid {
+            write!(f, "driver_uuid={};", to_hex(driver_uuid))?;
+        }
+        if let Some(luid) = &self.luid {
+            write!(f, "luid={};", to_hex(luid))?;
+        }
+        if let Some(pci) = &self.pci {
+            write!(
+                f,
+                "pci={:04x}:{:02x}:{:02x}.{:x};",
+                pci.domain, pci.bus, pci.device, pci.function
+            )?;
+        }
+        write!(
+            f,
+            "platform={};vendor={};name={};index={}",
+            escape(&self.platform),
+            escape(&self.vendor),
+            escape(&self.name),
+            self.index
+        )
+    }
+}
+
+fn parse_pci(text: &str) -> Option<PciBusInfo> {
+    let (domain, rest) = text.split_once(':')?;
+    let (bus, rest) = rest.split_once(':')?;
+    let (device, function) = rest.split_once('.')?;
+    Some(PciBusInfo {
+        domain: cl_uint::from_str_radix(domain, 16).ok()?,
+        bus: cl_uint::from_str_radix(bus, 16).ok()?,
+        device: cl_uint::from_str_radix(device, 16).ok()?,
+        function: cl_uint::from_str_radix(function, 16).ok()?,
+    })
+}
+
+/// Parses the string format of [DeviceIdentity]'s Display,
+/// returns CL_INVALID_VALUE if the string is not a valid DeviceIdentity.
+impl FromStr for DeviceIdentity {
+    type Err = ClError;
+
+    fn from_str(text: &str) -> result::Result<Self, Self::Err> {
+        let mut identity = Self::default();
+        for field in text.split(';') {
+            let (key, value) = field.split_once('=').ok_or(ClError(CL_INVALID_VALUE))?;
+            let valid = match key {
+                "uuid" => {
+                    identity.uuid = from_hex(value);
+                    identity.uuid.is_some()
+                }
+                "driver_uuid" => {
+                    identity.driver_uuid = from_hex(value);
+                    identity.driver_uuid.is_some()
+                }
+                "luid" => {
+                    identity.luid = from_hex(value);
+                    identity.luid.is_some()
+                }
+                "pci" => {
+                    identity.pci = parse_pci(value);
+                    identity.pci.is_some()
+                }
+                "platform" => {
+                    identity.platform = unescape(value);
+                    true
+                }
+                "vendor" => {
+                    identity.vendor = unescape(value);
+                    true
+                }
+                "name" => {
+                    identity.name = unescape(value);
+                    true
+                }
+                "index" => value.parse().map(|index| identity.index = index).is_ok(),
+                _ => false,
+            };
+            if !valid {
+                return Err(ClError(CL_INVALID_VALUE));
+            }
+        }
+        Ok(identity)
+    }
+}
+
+/// A snapshot of the information about an OpenCL device, see [Device::info].
+///
+/// Each field is named after the [Device] method used to query it and is None
+/// if the device does not support the query, e.g. the vendor specific
+/// `_amd`, `_nv` and `_intel` queries on other vendors' devices.  
+/// The handles returned by `platform` and `parent_device` and the volatile
+/// `reference_count` are not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceInfo {
+    pub dev_type: Option<cl_device_type>,
+    pub vendor_id: Option<cl_uint>,
+    pub max_compute_units: Option<cl_uint>,
+    pub max_work_item_dimensions: Option<cl_uint>,
+    pub max_work_group_size: Option<size_t>,
+    pub max_work_item_sizes: Option<Vec<size_t>>,
+    pub max_preferred_vector_width_char: Option<cl_uint>,
+    pub max_preferred_vector_width_short: Option<cl_uint>,
+    pub max_preferred_vector_width_int: Option<cl_uint>,
+    pub max_preferred_vector_width_long: Option<cl_uint>,
+    pub max_preferred_vector_width_float: Option<cl_uint>,
+    pub max_preferred_vector_width_double: Option<cl_uint>,
+    pub max_clock_frequency: Option<cl_uint>,
+    pub address_bits: Option<cl_uint>,
+    pub max_read_image_args: Option<cl_uint>,
+    pub max_write_image_args: Option<cl_uint>,
+    pub max_mem_alloc_size: Option<cl_ulong>,
+    pub image2d_max_width: Option<size_t>,
+    pub image2d_max_height: Option<size_t>,
+    pub image3d_max_width: Option<size_t>,
+    pub image3d_max_height: Option<size_t>,
+    pub image3d_max_depth: Option<size_t>,
+    pub image_support: Option<bool>,
+    pub max_parameter_size: Option<size_t>,
+    pub max_device_samples: Option<cl_uint>,
+    pub mem_base_addr_align: Option<cl_uint>,
+    pub min_data_type_align_size: Option<cl_uint>,
+    pub single_fp_config: Option<cl_ulong>,
+    pub global_mem_cache_type: Option<cl_uint>,
+    pub global_mem_cacheline_size: Option<cl_uint>,
+    pub global_mem_cache_size: Option<cl_ulong>,
+    pub global_mem_size: Option<cl_ulong>,
+    pub max_constant_buffer_size: Option<cl_ulong>,
+    pub max_constant_args: Option<cl_uint>,
+    pub local_mem_type: Option<cl_uint>,
+    pub local_mem_size: Option<cl_ulong>,
+    pub error_correction_support: Option<bool>,
+    pub profiling_timer_resolution: Option<size_t>,
+    pub endian_little: Option<bool>,
+    pub available: Option<bool>,
+    pub compiler_available: Option<bool>,
+    pub execution_capabilities: Option<cl_ulong>,
+    pub queue_on_host_properties: Option<cl_ulong>,
+    pub name: Option<String>,
+    pub vendor: Option<String>,
+    pub driver_version: Option<String>,
+    pub profile: Option<String>,
+    pub version: Option<String>,
+    pub extensions: Option<String>,
+    pub double_fp_config: Option<cl_ulong>,
+    pub half_fp_config: Option<cl_ulong>,
+    pub preferred_vector_width_half: Option<cl_uint>,
+    pub host_unified_memory: Option<bool>,
+    pub native_vector_width_char: Option<cl_uint>,
+    pub native_vector_width_short: Option<cl_uint>,
+    pub native_vector_width_int: Option<cl_uint>,
+    pub native_vector_width_long: Option<cl_uint>,
+    pub native_vector_width_float: Option<cl_uint>,
+    pub native_vector_width_double: Option<cl_uint>,
+    pub native_vector_width_half: Option<cl_uint>,
+    pub opencl_c_version: Option<String>,
+    pub linker_available: Option<bool>,
+    pub built_in_kernels: Option<String>,
+    pub image_max_buffer_size: Option<size_t>,
+    pub image_max_array_size: Option<size_t>,
+    pub partition_max_sub_devices: Option<cl_uint>,
+    pub partition_properties: Option<Vec<intptr_t>>,
+    pub partition_affinity_domain: Option<Vec<cl_ulong>>,
+    pub partition_type: Option<Vec<intptr_t>>,
+    pub preferred_interop_user_sync: Option<bool>,
+    pub printf_buffer_size: Option<size_t>,
+    pub image_pitch_alignment: Option<cl_uint>,
+    pub image_base_address_alignment: Option<cl_uint>,
+    pub max_read_write_image_args: Option<cl_uint>,
+    pub max_global_variable_size: Option<size_t>,
+    pub queue_on_device_properties: Option<Vec<intptr_t>>,
+    pub queue_on_device_preferred_size: Option<size_t>,
+    pub queue_on_device_max_size: Option<size_t>,
+    pub max_on_device_queues: Option<cl_uint>,
+    pub max_on_device_events: Option<cl_uint>,
+    pub svm_capabilities: Option<cl_device_svm_capabilities>,
+    pub global_variable_preferred_total_size: Option<size_t>,
+    pub max_pipe_args: Option<cl_uint>,
+    pub pipe_max_active_reservations: Option<cl_uint>,
+    pub pipe_max_packet_size: Option<cl_uint>,
+    pub preferred_platform_atomic_alignment: Option<cl_uint>,
+    pub preferred_global_atomic_alignment: Option<cl_uint>,
+    pub preferred_local_atomic_alignment: Option<cl_uint>,
+    pub il_version: Option<String>,
+    pub max_num_sub_groups: Option<cl_uint>,
+    pub sub_group_independent_forward_progress: Option<bool>,
+    pub numeric_version: Option<cl_uint>,
+    pub extensions_with_version: Option<Vec<NameVersion>>,
+    pub ils_with_version: Option<Vec<NameVersion>>,
+    pub built_in_kernels_with_version: Option<Vec<NameVersion>>,
+    pub atomic_memory_capabilities: Option<cl_ulong>,
+    pub atomic_fence_capabilities: Option<cl_ulong>,
+    pub non_uniform_work_group_support: Option<bool>,
+    pub opencl_c_all_versions: Option<Vec<NameVersion>>,
+    pub preferred_work_group_size_multiple: Option<size_t>,
+    pub work_group_collective_functions_support: Option<bool>,
+    pub generic_address_space_support: Option<bool>,
+    pub uuid_khr: Option<[u8; CL_UUID_SIZE_KHR]>,
+    pub driver_uuid_khr: Option<[u8; CL_UUID_SIZE_KHR]>,
+    pub luid_valid_khr: Option<bool>,
+    pub luid_khr: Option<[u8; CL_LUID_SIZE_KHR]>,
+    pub node_mask_khr: Option<cl_uint>,
+    pub opencl_c_features: Option<Vec<NameVersion>>,
+    pub device_enqueue_capabilities: Option<cl_ulong>,
+    pub pipe_support: Option<bool>,
+    pub latest_conformance_version_passed: Option<String>,
+    pub integer_dot_product_capabilities_khr: Option<cl_ulong>,
+    pub integer_dot_product_acceleration_properties_8bit_khr:
+        Option<IntegerDotProductAccelerationProperties>,
+    pub integer_dot_product_acceleration_properties_4x8bit_packed_khr:
+        Option<IntegerDotProductAccelerationProperties>,
+    pub compute_capability_major_nv: Option<cl_uint>,
+    pub compute_capability_minor_nv: Option<cl_uint>,
+    pub registers_per_block_nv: Option<cl_uint>,
+    pub wrap_size_nv: Option<cl_uint>,
+    pub gpu_overlap_nv: Option<cl_uint>,
+    pub compute_kernel_exec_timeout_nv: Option<cl_uint>,
+    pub integrated_memory_nv: Option<cl_uint>,
+    pub pci_bus_id_nv: Option<cl_uint>,
+    pub pci_slot_id_nv: Option<cl_uint>,
+    pub profiling_timer_offset_amd: Option<size_t>,
+    pub topology_amd: Option<PciBusInfo>,
+    pub pci_bus_id_amd: Option<cl_uint>,
+    pub pcibusinfokhr_intel: Option<PciBusInfo>,
+    pub pci_bus_id_intel: Option<cl_uint>,
+    pub board_name_amd: Option<String>,
+    pub global_free_memory_amd: Option<size_t>,
+    pub simd_per_compute_unit_amd: Option<cl_uint>,
+    pub simd_width_amd: Option<cl_uint>,
+    pub simd_instruction_width_amd: Option<cl_uint>,
+    pub wavefront_width_amd: Option<cl_uint>,
+    pub global_mem_channels_amd: Option<cl_uint>,
+    pub global_mem_channel_banks_amd: Option<cl_uint>,
+    pub global_mem_channel_bank_width_amd: Option<cl_uint>,
+    pub local_mem_size_per_compute_unit_amd: Option<cl_uint>,
+    pub local_mem_banks_amd: Option<cl_uint>,
+    pub thread_trace_supported_amd: Option<cl_uint>,
+    pub gfxip_major_amd: Option<cl_uint>,
+    pub gfxip_minor_amd: Option<cl_uint>,
+    pub available_async_queues_amd: Option<cl_uint>,
+    pub preferred_work_group_size_amd: Option<size_t>,
+    pub max_work_group_size_amd: Option<size_t>,
+    pub preferred_constant_buffer_size_amd: Option<size_t>,
+    pub pcie_id_amd: Option<cl_uint>,
+    pub device_ip_version_intel: Option<cl_uint>,
+    pub device_id_intel: Option<cl_uint>,
+    pub device_num_slices_intel: Option<cl_uint>,
+    pub device_num_sub_slices_per_slice_intel: Option<cl_uint>,
+    pub device_num_eus_per_sub_slice_intel: Option<cl_uint>,
+    pub device_num_threads_per_eu_intel: Option<cl_uint>,
+    pub device_feature_capabilities_intel: Option<cl_device_feature_capabilities_intel>,
+    pub device_external_memory_import_handle_types_khr: Option<Vec<u32>>,
+    pub device_semaphore_import_handle_types_khr: Option<Vec<u32>>,
+    pub device_semaphore_export_handle_types_khr: Option<Vec<u32>>,
+    pub device_semaphore_types_khr: Option<Vec<u32>>,
+    pub device_command_buffer_capabilities_khr: Option<cl_ulong>,
+    pub device_command_buffer_required_queue_properties_khr: Option<cl_ulong>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1019,6 +2611,22 @@ mod tests {
 
             println!("sub_devices len: {}", sub_devices.len());
             assert!(0 < sub_devices.len());
+
+            let sub_devices = device.partition(&Partition::Equally(2)).unwrap();
+            println!("Partition::Equally(2) len: {}", sub_devices.len());
+            assert!(0 < sub_devices.len());
+            for sub_device in &sub_devices {
+                assert_eq!(2, sub_device.max_compute_units().unwrap());
+                assert_eq!(device.id(), sub_device.parent_device().unwrap());
+            }
+
+            let counts = [1, 1];
+            let sub_devices = device.partition(&Partition::ByCounts(&counts)).unwrap();
+            assert_eq!(counts.len(), sub_devices.len());
+
+            let too_many = vec![1; device.partition_max_sub_devices().unwrap() as usize + 1];
+            assert!(device.partition(&Partition::ByCounts(&too_many)).is_err());
+            assert!(device.partition(&Partition::Equally(0)).is_err());
         } else {
             println!("OpenCL device capable of sub division not found");
         }
@@ -2187,6 +3795,242 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_opencl_version() {
+        assert_eq!(
+            Some(OpenClVersion::new(2, 1, 0)),
+            OpenClVersion::parse("OpenCL 2.1 NEO")
+        );
+        assert_eq!(
+            Some(OpenClVersion::new(1, 2, 0)),
+            OpenClVersion::parse("OpenCL C 1.2 ")
+        );
+        assert_eq!(
+            Some(OpenClVersion::new(3, 0, 0)),
+            OpenClVersion::parse("OpenCL 3.0 CUDA 12.2.148")
+        );
+        assert_eq!(
+            Some(OpenClVersion::new(3, 0, 14)),
+            OpenClVersion::parse("3.0.14")
+        );
+        assert_eq!(None, OpenClVersion::parse("OpenCL"));
+
+        let version = OpenClVersion::new(3, 0, 14);
+        let packed: cl_version = version.into();
+        assert_eq!(version, OpenClVersion::from(packed));
+        assert_eq!("3.0.14", version.to_string());
+
+        assert!(OpenClVersion::new(1, 2, 0) < OpenClVersion::new(2, 0, 0));
+        assert!(OpenClVersion::new(2, 0, 0) < OpenClVersion::new(2, 0, 1));
+        assert!(version.is_at_least(2, 0));
+        assert!(!version.is_at_least(3, 1));
+
+        let device_ids = get_all_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < device_ids.len());
+
+        let device = Device::new(device_ids[0]);
+        let version = device.opencl_version().unwrap();
+        println!("Device OpenCL version: {}", version);
+        assert!(device.supports_version(version.major, version.minor));
+        assert!(!device.supports_version(version.major + 1, 0));
+        println!(
+            "Device OpenCL C version: {}",
+            device.opencl_c_numeric_version().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_extensions() {
+        let extensions = Extensions::parse("cl_khr_fp64 cl_khr_command_buffer  cl_khr_icd ");
+        assert_eq!(3, extensions.len());
+        assert!(extensions.contains("cl_khr_fp64"));
+        assert!(extensions.supports(KnownExtension::KhrCommandBuffer));
+        assert!(!extensions.supports(KnownExtension::KhrSemaphore));
+        assert_eq!(None, extensions.version("cl_khr_fp64"));
+        assert_eq!(
+            vec![KnownExtension::KhrSemaphore],
+            extensions.missing(&[KnownExtension::KhrIcd, KnownExtension::KhrSemaphore])
+        );
+
+        for extension in KnownExtension::ALL {
+            assert_eq!(Some(extension), KnownExtension::from_name(extension.name()));
+        }
+        assert_eq!(None, KnownExtension::from_name("cl_khr_fp64"));
+
+        let device_ids = get_all_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < device_ids.len());
+
+        let device = Device::new(device_ids[0]);
+        let extensions = device.extension_set().unwrap();
+        println!("Number of device extensions: {}", extensions.len());
+        for name in device.extensions().unwrap().split_whitespace() {
+            assert!(extensions.contains(name));
+        }
+        for (name, version) in extensions.iter() {
+            println!("{}: {:?}", name, version);
+        }
+        println!(
+            "Missing enabled extensions: {:?}",
+            extensions.missing(&KnownExtension::enabled_features())
+        );
+    }
+
+    #[test]
+    fn test_device_flags() {
+        let device_type = DeviceType::CPU | DeviceType::GPU;
+        assert!(device_type.contains(DeviceType::GPU));
+        assert!(!device_type.contains(DeviceType::ACCELERATOR));
+        assert!(DeviceType::ALL.contains(device_type));
+        assert_eq!(CL_DEVICE_TYPE_CPU | CL_DEVICE_TYPE_GPU, device_type.bits());
+        assert_eq!("DeviceType(CPU | GPU)", format!("{:?}", device_type));
+
+        let fp_config = FpConfig::from(CL_FP_FMA | CL_FP_INF_NAN);
+        assert_eq!("FpConfig(INF_NAN | FMA)", format!("{:?}", fp_config));
+        assert_eq!(
+            "SvmCapabilities(COARSE_GRAIN_BUFFER)",
+            format!("{:?}", SvmCapabilities::COARSE_GRAIN_BUFFER)
+        );
+
+        let device_ids = get_all_devices(DeviceType::GPU).unwrap();
+        assert!(0 < device_ids.len());
+        let device = Device::new(device_ids[0]);
+        println!(
+            "Device type: {:?}",
+            DeviceType::from(device.dev_type().unwrap())
+        );
+        println!(
+            "Single fp config: {:?}",
+            FpConfig::from(device.single_fp_config().unwrap())
+        );
+        println!(
+            "SVM capabilities: {:?}",
+            SvmCapabilities::from(device.svm_mem_capability())
+        );
+    }
+
+    #[test]
+    fn test_device_selector() {
+        let all_devices = DeviceSelector::new().select().unwrap();
+        println!("Number of devices: {}", all_devices.len());
+        assert!(0 < all_devices.len());
+
+        let gpu_devices = DeviceSelector::new()
+            .set_device_type(CL_DEVICE_TYPE_GPU)
+            .set_min_version(1, 2)
+            .set_score(|device| i64::from(device.max_compute_units().unwrap_or_default()))
+            .select()
+            .unwrap();
+        println!("Number of GPU devices: {}", gpu_devices.len());
+        assert!(
+            gpu_devices
+                .windows(2)
+                .all(|pair| pair[1].score <= pair[0].score)
+        );
+
+        for selected in gpu_devices {
+            println!(
+                "{}: {}, score: {}",
+                selected.platform.name().unwrap(),
+                selected.device.name().unwrap(),
+                selected.score
+            );
+        }
+
+        let no_devices = DeviceSelector::new()
+            .set_extension("cl_not_a_real_extension")
+            .select()
+            .unwrap();
+        assert!(no_devices.is_empty());
+    }
+
+    #[test]
+    fn test_device_identity() {
+        let identities = device_identities().unwrap();
+        assert!(0 < identities.len());
+
+        for (device, identity) in &identities {
+            let text = identity.to_string();
+            println!("DeviceIdentity: {}", text);
+
+            let parsed: DeviceIdentity = text.parse().unwrap();
+            assert_eq!(*identity, parsed);
+
+            let resolved = parsed.resolve().unwrap();
+            assert_eq!(device.id(), resolved.id());
+            assert_eq!(*identity, device.identity().unwrap());
+        }
+
+        let identity = DeviceIdentity {
+            pci: Some(PciBusInfo {
+                domain: 0,
+                bus: 1,
+                device: 0,
+                function: 0,
+            }),
+            platform: String::from("a;b=c%"),
+            ..Default::default()
+        };
+        let text = identity.to_string();
+        assert_eq!(
+            "pci=0000:01:00.0;platform=a%3Bb%3Dc%25;vendor=;name=;index=0",
+            text
+        );
+        assert_eq!(identity, text.parse().unwrap());
+        assert!("uuid=0;index=0".parse::<DeviceIdentity>().is_err());
+    }
+
+    #[test]
+    fn test_device_info_snapshot() {
+        let device_ids = get_all_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < device_ids.len());
+
+        let device = Device::new(device_ids[0]);
+        let info = device.info();
+        println!("DeviceInfo: {:?}", info);
+
+        assert_eq!(device.name().unwrap(), info.name.clone().unwrap());
+        assert_eq!(device.version().unwrap(), info.version.clone().unwrap());
+
+        // Exclude the volatile free memory from the comparison
+        let mut info = info;
+        let mut value = device.info();
+        info.global_free_memory_amd = None;
+        value.global_free_memory_amd = None;
+        assert_eq!(info, value);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&info).unwrap();
+            println!("DeviceInfo json: {}", json);
+            let value: DeviceInfo = serde_json::from_str(&json).unwrap();
+            assert_eq!(info, value);
+        }
+    }
+
+    #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
+    #[test]
+    fn test_check_partition_counts() {
+        assert!(check_partition_counts(&[1, 2, 3], 4, 6).is_ok());
+
+        // Empty, too many sub-devices and too many compute units
+        assert!(check_partition_counts(&[], 4, 6).is_err());
+        assert!(check_partition_counts(&[1; 5], 4, 6).is_err());
+        assert!(check_partition_counts(&[4, 3], 4, 6).is_err());
+
+        // A zero count would terminate the list of counts
+        assert_eq!(
+            CL_INVALID_DEVICE_PARTITION_COUNT,
+            check_partition_counts(&[2, 0, 2], 4, 6).unwrap_err().0
+        );
+        // A total that overflows
+        assert_eq!(
+            CL_INVALID_DEVICE_PARTITION_COUNT,
+            check_partition_counts(&[cl_uint::MAX, 2], 4, 6)
+                .unwrap_err()
+                .0
+        );
+    }
+
     #[test]
     fn test_public_re_export() {
         assert_eq!(
@@ -15,7 +15,35 @@
 pub use cl3::event::*;
 
 use super::Result;
+use super::callback;
+use super::command_queue::CommandQueue;
+use super::context::Context;
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+use super::device::Device;
+use super::release::{check_release, release_error};
+use cl3::command_queue::flush;
+use cl3::error_codes::{
+    CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST, CL_INVALID_OPERATION, ClError,
+};
+#[cfg(any(feature = "cl_img_cancel_command", feature = "dynamic"))]
+use cl3::ext;
 use libc::c_void;
+use std::error;
+use std::fmt;
+use std::future::{Future, IntoFuture};
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ops::Deref;
+use std::pin::Pin;
+use std::result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{self, Poll, Waker};
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+use std::thread;
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 /// An OpenCL event object.
 ///
@@ -41,7 +69,14 @@ impl From<Event> for cl_event {
 
 impl Drop for Event {
     fn drop(&mut self) {
-        unsafe { release_event(self.event).expect("Error: clReleaseEvent") };
+        check_release("clReleaseEvent", unsafe { release_event(self.event) });
+    }
+}
+
+impl Clone for Event {
+    /// Retain the OpenCL event, so that both Events share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.event) }.expect("Error: clRetainEvent")
     }
 }
 
@@ -58,17 +93,86 @@ impl Event {
         Self { event }
     }
 
+    /// Create an Event from an OpenCL cl_event that is owned elsewhere.  
+    /// Calls clRetainEvent, so that the Event can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `event` must be a valid OpenCL cl_event.
+    pub unsafe fn from_raw_retained(event: cl_event) -> Result<Self> {
+        unsafe { retain_event(event)? };
+        Ok(Self::new(event))
+    }
+
     /// Get the underlying OpenCL cl_event.
     pub const fn get(&self) -> cl_event {
         self.event
     }
 
+    /// Release the OpenCL event, returning any error from clReleaseEvent instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { release_event(this.event) }?)
+    }
+
     /// Wait for the event to complete.
     pub fn wait(&self) -> Result<()> {
         let events = [self.get()];
         Ok(wait_for_events(&events)?)
     }
 
+    /// Wait for the event to complete or for a timeout to elapse, e.g. to
+    /// detect a hung command.  
+    /// Uses an event callback, the command continues after a timeout,
+    /// see [Event::cancel].
+    ///
+    /// * `timeout` - the maximum time to wait.
+    ///
+    /// returns an empty Result, WaitTimeoutError::Timeout if the timeout
+    /// elapsed first, or the error status of a terminated command or the
+    /// error code from the OpenCL C API function.
+    pub fn wait_timeout(&self, timeout: Duration) -> result::Result<(), WaitTimeoutError> {
+        let deadline = Instant::now().checked_add(timeout);
+        let signal = Arc::new(Signal::new());
+        let completed = Arc::clone(&signal);
+        self.on_complete(move |result| completed.set(result))?;
+        self.flush_command_queue()?;
+
+        // A timeout too long to represent is treated as no timeout
+        let result = match deadline {
+            Some(deadline) => signal
+                .wait_until(deadline)
+                .ok_or(WaitTimeoutError::Timeout)?,
+            None => signal.wait(),
+        };
+        Ok(result?)
+    }
+
+    /// Cancel the event's command if it has not completed, e.g. after
+    /// [Event::wait_timeout] has timed out.  
+    /// Calls clCancelCommandsIMG.
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    #[cfg(any(feature = "cl_img_cancel_command", feature = "dynamic"))]
+    pub fn cancel(&self) -> Result<()> {
+        let events = [self.event];
+        Ok(ext::cancel_commands_img(
+            events.as_ptr(),
+            events.len() as cl_uint,
+        )?)
+    }
+
+    /// Flush the event's command-queue, since its command may not be
+    /// submitted until then, so that an event callback can be called.
+    fn flush_command_queue(&self) -> Result<()> {
+        let queue = self.command_queue()?;
+        if !queue.is_null() {
+            flush(queue)?;
+        }
+        Ok(())
+    }
+
     pub fn command_execution_status(&self) -> Result<CommandExecutionStatus> {
         Ok(CommandExecutionStatus(
             get_event_info(self.event, CL_EVENT_COMMAND_EXECUTION_STATUS)?.into(),
@@ -93,6 +197,26 @@ impl Event {
         Ok(isize::from(get_event_info(self.event, CL_EVENT_CONTEXT)?) as cl_context)
     }
 
+    /// The CommandQueue of the event, retained so that it can outlive the Event,
+    /// e.g. to enqueue more commands when the event completes.
+    ///
+    /// returns a Result containing the CommandQueue, or None for a user event
+    /// which does not have a command-queue,
+    /// or the error code from the OpenCL C API function.
+    pub fn retained_command_queue(&self) -> Result<Option<CommandQueue>> {
+        let queue = self.command_queue()?;
+        if queue.is_null() {
+            Ok(None)
+        } else {
+            unsafe { CommandQueue::from_raw_retained(queue).map(Some) }
+        }
+    }
+
+    /// The Context of the event, retained so that it can outlive the Event.
+    pub fn retained_context(&self) -> Result<Context> {
+        unsafe { Context::from_raw_retained(self.context()?) }
+    }
+
     /// Get data about an OpenCL event.
     /// Calls clGetEventInfo to get the desired data about the event.
     pub fn get_data(&self, param_name: cl_event_info) -> Result<Vec<u8>> {
@@ -113,6 +237,42 @@ impl Event {
         )?)
     }
 
+    /// Call a closure when the event's command reaches an execution status,
+    /// instead of an `extern "C"` function with a raw user_data pointer,
+    /// see [Event::set_callback].  
+    /// The closure is called once and then freed, it may be called on any
+    /// thread and any panic in it is caught.
+    ///
+    /// * `status` - CL_SUBMITTED, CL_RUNNING or CL_COMPLETE.
+    /// * `callback` - called with the execution status of the command, or
+    ///   the error status if the command was terminated, e.g.
+    ///   CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST.
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    pub fn on_status<F>(&self, status: CommandExecutionStatus, callback: F) -> Result<()>
+    where
+        F: FnOnce(Result<CommandExecutionStatus>) + Send + 'static,
+    {
+        callback::register_once(callback, |user_data| {
+            self.set_callback(status.0, event_status_callback, user_data)
+        })
+    }
+
+    /// Call a closure when the event's command has completed, see [Event::on_status].
+    ///
+    /// * `callback` - called with an empty Result, or the error status if the
+    ///   command was terminated.
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    pub fn on_complete<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(Result<()>) + Send + 'static,
+    {
+        self.on_status(CommandExecutionStatus(CL_COMPLETE), move |status| {
+            callback(status.map(|_| ()));
+        })
+    }
+
     pub fn profiling_command_queued(&self) -> Result<cl_ulong> {
         Ok(get_event_profiling_info(self.event, CL_PROFILING_COMMAND_QUEUED)?.into())
     }
@@ -141,16 +301,833 @@ impl Event {
     }
 }
 
+/// The error from waiting for commands with a timeout,
+/// see [Event::wait_timeout].
+#[derive(Debug)]
+pub enum WaitTimeoutError {
+    /// The timeout elapsed before the commands completed.
+    Timeout,
+    /// The error status of a terminated command or the error code from an
+    /// OpenCL C API function.
+    Cl(ClError),
+}
+
+impl WaitTimeoutError {
+    /// Whether the timeout elapsed before the commands completed.
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout)
+    }
+}
+
+impl PartialEq for WaitTimeoutError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Timeout, Self::Timeout) => true,
+            (Self::Cl(error), Self::Cl(other)) => error.0 == other.0,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for WaitTimeoutError {}
+
+impl From<ClError> for WaitTimeoutError {
+    fn from(error: ClError) -> Self {
+        Self::Cl(error)
+    }
+}
+
+impl fmt::Display for WaitTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => f.write_str("timed out waiting for OpenCL commands"),
+            Self::Cl(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl error::Error for WaitTimeoutError {}
+
+/// A value set once by an event callback, which other threads can wait for.
+#[derive(Debug)]
+struct Signal<T> {
+    value: Mutex<Option<T>>,
+    condvar: Condvar,
+}
+
+impl<T> Signal<T> {
+    const fn new() -> Self {
+        Self {
+            value: Mutex::new(None),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Set the value, unless it has already been set.
+    fn set(&self, value: T) {
+        let mut current = self.value.lock().unwrap_or_else(|e| e.into_inner());
+        if current.is_none() {
+            *current = Some(value);
+            drop(current);
+            self.condvar.notify_all();
+        }
+    }
+
+    /// Wait for the value to be set.
+    fn wait(&self) -> T {
+        let mut value = self.value.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(value) = value.take() {
+                return value;
+            }
+            value = self.condvar.wait(value).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Wait for the value to be set or for a deadline to pass.
+    ///
+    /// returns the value, or None if the deadline passed first.
+    fn wait_until(&self, deadline: Instant) -> Option<T> {
+        let mut value = self.value.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(value) = value.take() {
+                return Some(value);
+            }
+            let timeout = deadline.checked_duration_since(Instant::now())?;
+            value = self
+                .condvar
+                .wait_timeout(value, timeout)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
+
+/// An OpenCL user event, whose execution status is set by the host, so that
+/// commands can wait for host-side work, e.g. I/O, to finish.
+///
+/// Derefs to an [Event], so it can be placed in any event_wait_list, e.g. with
+/// `user_event.get()` or [ExecuteKernel::set_wait_event](crate::kernel::ExecuteKernel::set_wait_event).  
+/// Implements the Drop trait to fail the event with
+/// CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST if it is still pending when
+/// dropped, so that commands waiting for it cannot deadlock the device.
+#[derive(Debug)]
+pub struct UserEvent {
+    event: Event,
+    pending: AtomicBool,
+}
+
+impl Deref for UserEvent {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl Drop for UserEvent {
+    fn drop(&mut self) {
+        let result = self
+            .is_pending()
+            .then(|| self.set_status(CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST));
+        if let Some(Err(e)) = result {
+            release_error("clSetUserEventStatus", e);
+        }
+    }
+}
+
+impl UserEvent {
+    /// Create a pending user event for a context.
+    ///
+    /// * `context` - the OpenCL context of the commands that wait for the event.
+    ///
+    /// returns a Result containing the new UserEvent
+    /// or the error code from the OpenCL C API function.
+    pub fn new(context: &Context) -> Result<Self> {
+        let event = create_user_event(context.get())?;
+        Ok(Self {
+            event: Event::new(event),
+            pending: AtomicBool::new(true),
+        })
+    }
+
+    /// Whether the execution status of the user event has not been set.
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    /// Set the execution status of the user event to CL_COMPLETE,
+    /// so that the commands waiting for it can run.
+    ///
+    /// returns an empty Result, CL_INVALID_OPERATION if the status has already
+    /// been set, or the error code from the OpenCL C API function.
+    pub fn complete(&self) -> Result<()> {
+        self.set_status(CL_COMPLETE)
+    }
+
+    /// Set the execution status of the user event to an error,
+    /// so that the commands waiting for it are terminated.
+    ///
+    /// * `error` - the error, which must be negative.
+    ///
+    /// returns an empty Result, CL_INVALID_VALUE if the error is not negative,
+    /// CL_INVALID_OPERATION if the status has already been set,
+    /// or the error code from the OpenCL C API function.
+    pub fn fail(&self, error: ClError) -> Result<()> {
+        if 0 <= error.0 {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+        self.set_status(error.0)
+    }
+
+    fn set_status(&self, execution_status: cl_int) -> Result<()> {
+        if !self.pending.swap(false, Ordering::AcqRel) {
+            return Err(ClError(CL_INVALID_OPERATION));
+        }
+        set_user_event_status(self.event.get(), execution_status).map_err(|code| {
+            // The status is still pending, so that Drop can set it
+            self.pending.store(true, Ordering::Release);
+            ClError(code)
+        })
+    }
+}
+
+/// A list of events for an event_wait_list, which retains its events so
+/// that they cannot be released while their handles are in use.
+///
+/// Derefs to a slice of the cl_event handles of its events, so it can be
+/// passed to any `event_wait_list` parameter, e.g. of the `CommandQueue::enqueue_*`
+/// methods, with `&event_list`, or to
+/// [ExecuteKernel::set_event_list](crate::kernel::ExecuteKernel::set_event_list).
+#[derive(Clone, Debug, Default)]
+pub struct EventList {
+    events: Vec<Event>,
+    handles: Vec<cl_event>,
+}
+
+impl Deref for EventList {
+    type Target = [cl_event];
+
+    fn deref(&self) -> &[cl_event] {
+        &self.handles
+    }
+}
+
+impl From<Vec<Event>> for EventList {
+    fn from(events: Vec<Event>) -> Self {
+        let handles = events.iter().map(Event::get).collect();
+        Self { events, handles }
+    }
+}
+
+impl FromIterator<Event> for EventList {
+    fn from_iter<I: IntoIterator<Item = Event>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<Event> for EventList {
+    fn extend<I: IntoIterator<Item = Event>>(&mut self, iter: I) {
+        for event in iter {
+            self.push(event);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a EventList {
+    type Item = &'a Event;
+    type IntoIter = std::slice::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
+
+impl EventList {
+    /// Create an empty EventList.
+    pub const fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            handles: Vec::new(),
+        }
+    }
+
+    /// The events of the list.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Add an event to the list, e.g. the Event returned by an enqueue method
+    /// or a marker command.
+    pub fn push(&mut self, event: Event) {
+        self.handles.push(event.get());
+        self.events.push(event);
+    }
+
+    /// Add an event that is owned elsewhere to the list, e.g. a [UserEvent],
+    /// retaining it for the list.
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    pub fn push_retained(&mut self, event: &Event) -> Result<()> {
+        let event = unsafe { Event::from_raw_retained(event.get())? };
+        self.push(event);
+        Ok(())
+    }
+
+    /// Remove all the events from the list, releasing them.
+    pub fn clear(&mut self) {
+        self.handles.clear();
+        self.events.clear();
+    }
+
+    /// Wait for all the events of the list to complete.
+    ///
+    /// returns an empty Result, the error status of a terminated command,
+    /// or the error code from the OpenCL C API function.
+    pub fn wait_all(&self) -> Result<()> {
+        if self.handles.is_empty() {
+            Ok(())
+        } else {
+            Ok(wait_for_events(&self.handles)?)
+        }
+    }
+
+    /// Wait for any of the events of the list to complete, using event callbacks.
+    ///
+    /// returns a Result containing the index of the first event to complete,
+    /// CL_INVALID_VALUE if the list is empty, the error status of the first
+    /// command if it was terminated, or the error code from the OpenCL C API
+    /// function.
+    pub fn wait_any(&self) -> Result<usize> {
+        if self.events.is_empty() {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+
+        let first = Arc::new(Signal::new());
+        for (index, event) in self.events.iter().enumerate() {
+            let first = Arc::clone(&first);
+            event.on_complete(move |result| first.set(result.map(|()| index)))?;
+            event.flush_command_queue()?;
+        }
+        first.wait()
+    }
+
+    /// The numbers of the events of the list in each command execution status.
+    ///
+    /// returns a Result containing the EventListStatus
+    /// or the error code from the OpenCL C API function.
+    pub fn status(&self) -> Result<EventListStatus> {
+        let mut status = EventListStatus::default();
+        for event in &self.events {
+            match event.command_execution_status()?.0 {
+                CL_COMPLETE => status.complete += 1,
+                CL_RUNNING => status.running += 1,
+                CL_SUBMITTED => status.submitted += 1,
+                CL_QUEUED => status.queued += 1,
+                _ => status.failed += 1,
+            }
+        }
+        Ok(status)
+    }
+}
+
+/// The numbers of the events of an [EventList] in each command execution
+/// status, see [EventList::status].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventListStatus {
+    pub queued: usize,
+    pub submitted: usize,
+    pub running: usize,
+    pub complete: usize,
+    /// The number of events whose commands were terminated.
+    pub failed: usize,
+}
+
+impl EventListStatus {
+    /// Whether all the commands have completed or been terminated.
+    pub const fn is_finished(&self) -> bool {
+        0 == self.queued + self.submitted + self.running
+    }
+
+    /// Whether any command was terminated.
+    pub const fn has_failed(&self) -> bool {
+        0 < self.failed
+    }
+}
+
+impl IntoFuture for Event {
+    type Output = Result<()>;
+    type IntoFuture = EventFuture;
+
+    /// Await the completion of the event's command, see [EventFuture].
+    fn into_future(self) -> EventFuture {
+        EventFuture::new(self)
+    }
+}
+
+/// The clSetEventCallback pfn_notify function for [Event::on_status].
+extern "C" fn event_status_callback(
+    _event: cl_event,
+    event_command_status: cl_int,
+    user_data: *mut c_void,
+) {
+    let status = if event_command_status < 0 {
+        Err(ClError(event_command_status))
+    } else {
+        Ok(CommandExecutionStatus(event_command_status))
+    };
+    unsafe { callback::call_once(user_data, status) };
+}
+
+/// The state shared between an [EventFuture] and its event callback.
+#[derive(Debug, Default)]
+struct FutureState {
+    status: Option<cl_int>,
+    waker: Option<Waker>,
+}
+
+/// The clSetEventCallback pfn_notify function for [EventFuture].
+/// `user_data` is an `Arc<Mutex<FutureState>>` from `Arc::into_raw`.
+extern "C" fn wake_event_future(
+    _event: cl_event,
+    event_command_status: cl_int,
+    user_data: *mut c_void,
+) {
+    callback::catch_panic(|| {
+        let state = unsafe { Arc::from_raw(user_data as *const Mutex<FutureState>) };
+        let waker = {
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            state.status = Some(event_command_status);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    });
+}
+
+/// A Future that completes when the command of an OpenCL event has completed,
+/// so that the event can be awaited instead of blocking a thread in
+/// [Event::wait].
+///
+/// The first poll registers a CL_COMPLETE callback with clSetEventCallback
+/// and flushes the event's command-queue. The callback wakes the task when the
+/// command completes or is terminated, so the future does not depend on any
+/// particular executor.
+///
+/// The output is the error status of the command if it was terminated,
+/// e.g. CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct EventFuture {
+    event: Event,
+    state: Option<Arc<Mutex<FutureState>>>,
+}
+
+impl EventFuture {
+    /// Create an EventFuture for an Event, see also [Event::into_future].
+    pub const fn new(event: Event) -> Self {
+        Self { event, state: None }
+    }
+
+    /// The event of the future.
+    pub const fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Convert a command execution status to the output of the future.
+    const fn status_result(status: cl_int) -> Result<()> {
+        if status < 0 {
+            Err(ClError(status))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Register the callback that wakes the task and submit the command.
+    fn register(&self, state: &Arc<Mutex<FutureState>>) -> Result<()> {
+        let user_data = Arc::into_raw(state.clone()) as *mut c_void;
+        if let Err(e) = self
+            .event
+            .set_callback(CL_COMPLETE, wake_event_future, user_data)
+        {
+            drop(unsafe { Arc::from_raw(user_data as *const Mutex<FutureState>) });
+            return Err(e);
+        }
+
+        self.event.flush_command_queue()
+    }
+}
+
+impl Future for EventFuture {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if let Some(state) = &this.state {
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            return state.status.map_or_else(
+                || {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                },
+                |status| Poll::Ready(Self::status_result(status)),
+            );
+        }
+
+        let status = match this.event.command_execution_status() {
+            Ok(status) => status.0,
+            Err(e) => return Poll::Ready(Err(e)),
+        };
+        if CL_COMPLETE == status || status < 0 {
+            return Poll::Ready(Self::status_result(status));
+        }
+
+        let state = Arc::new(Mutex::new(FutureState {
+            status: None,
+            waker: Some(cx.waker().clone()),
+        }));
+        if let Err(e) = this.register(&state) {
+            return Poll::Ready(Err(e));
+        }
+        this.state = Some(state);
+        Poll::Pending
+    }
+}
+
+/// A non-blocking transfer between a host slice and an OpenCL memory object.
+///
+/// See [CommandQueue::read_async](crate::command_queue::CommandQueue::read_async)
+/// and [CommandQueue::write_async](crate::command_queue::CommandQueue::write_async).  
+/// Borrows the host slice until the transfer has completed, so that it
+/// cannot be used or dropped while the OpenCL implementation may access it.  
+/// Implements the Drop trait to wait for the transfer to complete, an error
+/// from the wait is passed to the release error handler, see
+/// [set_release_error_handler](crate::release::set_release_error_handler).
+#[must_use = "dropping a PendingTransfer blocks until the transfer has completed"]
+#[derive(Debug)]
+pub struct PendingTransfer<'a, T> {
+    event: Option<Event>,
+    _data: PhantomData<&'a [T]>,
+}
+
+impl<T> PendingTransfer<'_, T> {
+    pub(crate) const fn new(event: Option<Event>) -> Self {
+        Self {
+            event,
+            _data: PhantomData,
+        }
+    }
+
+    /// Take the event of the transfer without waiting for it, the caller
+    /// must keep the host data alive until the event has completed.
+    pub(crate) fn into_event(mut self) -> Option<Event> {
+        self.event.take()
+    }
+
+    /// The event of the transfer, None if there was nothing to transfer.
+    pub const fn event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    /// Whether the transfer has completed.
+    pub fn is_complete(&self) -> Result<bool> {
+        self.event.as_ref().map_or(Ok(true), |event| {
+            Ok(CL_COMPLETE == event.command_execution_status()?.0)
+        })
+    }
+
+    /// Wait for the transfer to complete, releasing the host slice.
+    pub fn wait(mut self) -> Result<()> {
+        self.event.take().map_or(Ok(()), |event| event.wait())
+    }
+}
+
+impl<T> Drop for PendingTransfer<'_, T> {
+    fn drop(&mut self) {
+        if let Some(Err(e)) = self.event.take().map(|event| event.wait()) {
+            release_error("clWaitForEvents", e);
+        }
+    }
+}
+
+/// A non-blocking transfer between an owned host vector and an OpenCL memory object.
+///
+/// See [CommandQueue::read_async_owned](crate::command_queue::CommandQueue::read_async_owned)
+/// and [CommandQueue::write_async_owned](crate::command_queue::CommandQueue::write_async_owned).  
+/// Owns the host vector until the transfer has completed, then [OwnedTransfer::wait]
+/// gives it back.  
+/// Implements the Drop trait to wait for the transfer to complete before
+/// dropping the vector, an error from the wait is passed to the release
+/// error handler, see [set_release_error_handler](crate::release::set_release_error_handler).
+#[must_use = "dropping an OwnedTransfer blocks until the transfer has completed"]
+#[derive(Debug)]
+pub struct OwnedTransfer<T> {
+    event: Option<Event>,
+    data: Vec<T>,
+}
+
+impl<T> OwnedTransfer<T> {
+    pub(crate) const fn new(event: Option<Event>, data: Vec<T>) -> Self {
+        Self { event, data }
+    }
+
+    /// The event of the transfer, None if there was nothing to transfer.
+    pub const fn event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    /// Whether the transfer has completed.
+    pub fn is_complete(&self) -> Result<bool> {
+        self.event.as_ref().map_or(Ok(true), |event| {
+            Ok(CL_COMPLETE == event.command_execution_status()?.0)
+        })
+    }
+
+    /// Wait for the transfer to complete.
+    ///
+    /// returns a Result containing the host vector
+    /// or the error code from the OpenCL C API function.
+    pub fn wait(mut self) -> Result<Vec<T>> {
+        if let Some(Err(e)) = self.event.take().map(|event| event.wait()) {
+            // The OpenCL implementation may still access the vector
+            mem::forget(mem::take(&mut self.data));
+            return Err(e);
+        }
+        Ok(mem::take(&mut self.data))
+    }
+}
+
+impl<T> Drop for OwnedTransfer<T> {
+    fn drop(&mut self) {
+        if let Some(Err(e)) = self.event.take().map(|event| event.wait()) {
+            // The OpenCL implementation may still access the vector
+            mem::forget(mem::take(&mut self.data));
+            release_error("clWaitForEvents", e);
+        }
+    }
+}
+
+/// A simultaneous sample of a device timer and the host clocks.
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+#[derive(Clone, Copy, Debug)]
+struct ClockSample {
+    device_timestamp: cl_ulong,
+    host_timestamp: cl_ulong,
+    instant: Instant,
+    system_time: SystemTime,
+}
+
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+impl ClockSample {
+    /// Sample the device and host timers, taking the host clocks at the
+    /// midpoint of the call to clGetDeviceAndHostTimer.
+    fn new(device: &Device) -> Result<Self> {
+        let before = Instant::now();
+        let system_time = SystemTime::now();
+        let [device_timestamp, host_timestamp] = device.get_device_and_host_timer()?;
+        let half_call = before.elapsed() / 2;
+        Ok(Self {
+            device_timestamp,
+            host_timestamp,
+            instant: before + half_call,
+            system_time: system_time + half_call,
+        })
+    }
+}
+
+/// A correlation between a device timer and the host clocks.
+///
+/// Converts device timer values, e.g. [Event] profiling timestamps, to host
+/// `Instant`s and `SystemTime`s, so that work on a device can be placed on
+/// the same timeline as work on the host.  
+/// Estimates the offset between the device and host timers and, when created
+/// with [ClockCorrelation::with_interval], the drift of the device timer.
+/// Requires the device to support clGetDeviceAndHostTimer, i.e. OpenCL 2.1.
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+#[derive(Clone, Copy, Debug)]
+pub struct ClockCorrelation {
+    sample: ClockSample,
+    drift: f64,
+}
+
+#[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+impl ClockCorrelation {
+    /// Create a ClockCorrelation from a single sample of the device and host
+    /// timers, assuming that the device timer does not drift.
+    ///
+    /// * `device` - the device to correlate with the host.
+    ///
+    /// returns a Result containing the new ClockCorrelation
+    /// or the error code from the OpenCL C API function.
+    pub fn new(device: &Device) -> Result<Self> {
+        Ok(Self {
+            sample: ClockSample::new(device)?,
+            drift: 1.0,
+        })
+    }
+
+    /// Create a ClockCorrelation from two samples of the device and host
+    /// timers taken `interval` apart, estimating the drift of the device timer.
+    /// Blocks the calling thread for `interval`.
+    ///
+    /// * `device` - the device to correlate with the host.
+    /// * `interval` - the time between samples, the longer the interval the
+    ///   more accurate the drift estimate.
+    ///
+    /// returns a Result containing the new ClockCorrelation
+    /// or the error code from the OpenCL C API function.
+    pub fn with_interval(device: &Device, interval: Duration) -> Result<Self> {
+        let first = ClockSample::new(device)?;
+        thread::sleep(interval);
+        let sample = ClockSample::new(device)?;
+
+        let device_ns = sample
+            .device_timestamp
+            .saturating_sub(first.device_timestamp);
+        let host_ns = sample.instant.duration_since(first.instant).as_nanos();
+        let drift = if 0 < device_ns && 0 < host_ns {
+            host_ns as f64 / device_ns as f64
+        } else {
+            1.0
+        };
+
+        Ok(Self { sample, drift })
+    }
+
+    /// The offset of the OpenCL host timer from the device timer in
+    /// nanoseconds at the time of the last sample.
+    pub const fn offset(&self) -> i128 {
+        self.sample.host_timestamp as i128 - self.sample.device_timestamp as i128
+    }
+
+    /// The estimated number of host nanoseconds per device nanosecond,
+    /// 1.0 if the device timer does not drift from the host clock.
+    pub const fn drift(&self) -> f64 {
+        self.drift
+    }
+
+    /// The number of host nanoseconds from the last sample to a device timer value,
+    /// negative if the device timer value is before the sample.
+    fn host_nanos_since_sample(&self, device_timestamp: cl_ulong) -> i128 {
+        let device_ns = i128::from(device_timestamp) - i128::from(self.sample.device_timestamp);
+        (device_ns as f64 * self.drift) as i128
+    }
+
+    /// Convert a device timer value to an OpenCL host timer value,
+    /// see clGetHostTimer.
+    pub fn to_host_timer(&self, device_timestamp: cl_ulong) -> cl_ulong {
+        let host_ns =
+            i128::from(self.sample.host_timestamp) + self.host_nanos_since_sample(device_timestamp);
+        host_ns.clamp(0, i128::from(cl_ulong::MAX)) as cl_ulong
+    }
+
+    /// Convert a device timer value to a host `Instant`.
+    ///
+    /// returns the `Instant`, or None if it cannot be represented,
+    /// e.g. for a device timer value long before the sample.
+    pub fn to_instant(&self, device_timestamp: cl_ulong) -> Option<Instant> {
+        let host_ns = self.host_nanos_since_sample(device_timestamp);
+        let duration = Duration::from_nanos(u64::try_from(host_ns.unsigned_abs()).ok()?);
+        if 0 <= host_ns {
+            self.sample.instant.checked_add(duration)
+        } else {
+            self.sample.instant.checked_sub(duration)
+        }
+    }
+
+    /// Convert a device timer value to a host `SystemTime`.
+    ///
+    /// returns the `SystemTime`, or None if it cannot be represented,
+    /// e.g. for a device timer value long before the sample.
+    pub fn to_system_time(&self, device_timestamp: cl_ulong) -> Option<SystemTime> {
+        let host_ns = self.host_nanos_since_sample(device_timestamp);
+        let duration = Duration::from_nanos(u64::try_from(host_ns.unsigned_abs()).ok()?);
+        if 0 <= host_ns {
+            self.sample.system_time.checked_add(duration)
+        } else {
+            self.sample.system_time.checked_sub(duration)
+        }
+    }
+
+    /// The host `Instant`s when an event's command started and ended execution.
+    /// The event must have been enqueued on a command queue with
+    /// CL_QUEUE_PROFILING_ENABLE and have completed.
+    ///
+    /// * `event` - the completed event.
+    ///
+    /// returns a Result containing the start and end `Instant`s,
+    /// CL_INVALID_VALUE if they cannot be represented,
+    /// or the error code from the OpenCL C API function.
+    pub fn event_instants(&self, event: &Event) -> Result<(Instant, Instant)> {
+        let instant = |device_timestamp| {
+            self.to_instant(device_timestamp)
+                .ok_or(ClError(CL_INVALID_VALUE))
+        };
+        Ok((
+            instant(event.profiling_command_start()?)?,
+            instant(event.profiling_command_end()?)?,
+        ))
+    }
+
+    /// The host `SystemTime`s when an event's command started and ended execution.
+    /// The event must have been enqueued on a command queue with
+    /// CL_QUEUE_PROFILING_ENABLE and have completed.
+    ///
+    /// * `event` - the completed event.
+    ///
+    /// returns a Result containing the start and end `SystemTime`s,
+    /// CL_INVALID_VALUE if they cannot be represented,
+    /// or the error code from the OpenCL C API function.
+    pub fn event_system_times(&self, event: &Event) -> Result<(SystemTime, SystemTime)> {
+        let system_time = |device_timestamp| {
+            self.to_system_time(device_timestamp)
+                .ok_or(ClError(CL_INVALID_VALUE))
+        };
+        Ok((
+            system_time(event.profiling_command_start()?)?,
+            system_time(event.profiling_command_end()?)?,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::command_queue::{CL_QUEUE_PROFILING_ENABLE, CommandQueue};
+    use crate::command_queue::{CL_QUEUE_PROFILING_ENABLE, CommandQueue, CommandQueueBuilder};
     use crate::context::Context;
     use crate::device::{CL_DEVICE_TYPE_GPU, Device};
+    use crate::error_codes::CL_SUCCESS;
     use crate::memory::{Buffer, CL_MEM_READ_ONLY};
     use crate::platform::get_platforms;
     use crate::types::{CL_NON_BLOCKING, cl_float};
+    use std::pin::pin;
     use std::ptr;
+    use std::sync::mpsc;
+    use std::task::Wake;
+    use std::thread;
+    use std::time::Duration;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    // A minimal executor, to show that EventFuture does not need a runtime
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = task::Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
 
     extern "C" fn event_callback_function(
         _event: cl_event,
@@ -222,6 +1199,15 @@ mod tests {
         let value = event.context().unwrap();
         assert!(context.get() == value);
 
+        let retained_queue = event.retained_command_queue().unwrap().unwrap();
+        assert!(queue.get() == retained_queue.get());
+        let retained_context = retained_queue.retained_context().unwrap();
+        assert!(context.get() == retained_context.get());
+        assert_eq!(context.devices(), retained_context.devices());
+        assert_eq!(device.id(), retained_queue.device_object().unwrap().id());
+        drop(retained_queue);
+        drop(retained_context);
+
         event.wait().unwrap();
 
         let value = event.command_execution_status().unwrap();
@@ -250,4 +1236,331 @@ mod tests {
             Err(e) => println!("OpenCL error, event.profiling_command_complete(): {}", e),
         }
     }
+
+    #[test]
+    fn test_event_callbacks() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_ONLY, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        let event = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &[])
+                .unwrap()
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let submitted = sender.clone();
+        event
+            .on_status(CommandExecutionStatus(CL_SUBMITTED), move |status| {
+                submitted.send(status.map(|status| status.0)).unwrap()
+            })
+            .unwrap();
+        event
+            .on_complete(move |result| sender.send(result.map(|()| CL_COMPLETE)).unwrap())
+            .unwrap();
+
+        // A panic in a callback is caught
+        event
+            .on_complete(|_| panic!("event callback panic"))
+            .unwrap();
+        queue.flush().unwrap();
+
+        let mut statuses = [
+            receiver
+                .recv_timeout(Duration::from_secs(10))
+                .unwrap()
+                .unwrap(),
+            receiver
+                .recv_timeout(Duration::from_secs(10))
+                .unwrap()
+                .unwrap(),
+        ];
+        statuses.sort();
+        assert_eq!([CL_COMPLETE, CL_SUBMITTED], statuses);
+        event.wait().unwrap();
+    }
+
+    #[test]
+    fn test_user_event() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_ONLY, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        // The write waits for the user event
+        let user_event = UserEvent::new(&context).unwrap();
+        assert!(user_event.is_pending());
+        assert_eq!(CL_COMMAND_USER, user_event.command_type().unwrap().0);
+        let event = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &[user_event.get()])
+                .unwrap()
+        };
+        queue.flush().unwrap();
+        assert_ne!(CL_COMPLETE, event.command_execution_status().unwrap().0);
+
+        user_event.complete().unwrap();
+        assert!(!user_event.is_pending());
+        event.wait().unwrap();
+        assert_eq!(CL_INVALID_OPERATION, user_event.complete().unwrap_err().0);
+        assert_eq!(
+            CL_INVALID_VALUE,
+            user_event.fail(ClError(CL_SUCCESS)).unwrap_err().0
+        );
+
+        // Dropping a pending user event terminates the commands that wait for it
+        let user_event = UserEvent::new(&context).unwrap();
+        let event = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &[user_event.get()])
+                .unwrap()
+        };
+        drop(user_event);
+        assert!(event.wait().is_err());
+        assert_eq!(
+            CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST,
+            event.command_execution_status().unwrap().0
+        );
+    }
+
+    #[test]
+    fn test_event_list() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_ONLY, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        let user_event = UserEvent::new(&context).unwrap();
+        let mut wait_list = EventList::new();
+        wait_list.push_retained(&user_event).unwrap();
+        assert_eq!([user_event.get()], *wait_list);
+
+        // The EventList retains the events of the write and marker commands
+        let mut events = EventList::new();
+        events.push(unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &wait_list)
+                .unwrap()
+        });
+        events.push(unsafe { queue.enqueue_marker_with_wait_list(&events).unwrap() });
+        assert_eq!(2, events.events().len());
+
+        let status = events.status().unwrap();
+        println!("event list status: {:?}", status);
+        assert!(!status.is_finished());
+
+        user_event.complete().unwrap();
+        assert_eq!(0, wait_list.wait_any().unwrap());
+        events.wait_all().unwrap();
+
+        let status = events.status().unwrap();
+        assert_eq!(2, status.complete);
+        assert!(status.is_finished());
+        assert!(!status.has_failed());
+
+        events.clear();
+        assert!(events.is_empty());
+        events.wait_all().unwrap();
+        assert_eq!(CL_INVALID_VALUE, events.wait_any().unwrap_err().0);
+    }
+
+    #[test]
+    fn test_wait_timeout() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_ONLY, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        // The write cannot complete until the user event is complete
+        let user_event = UserEvent::new(&context).unwrap();
+        let event = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &[user_event.get()])
+                .unwrap()
+        };
+
+        let error = event.wait_timeout(Duration::from_millis(10)).unwrap_err();
+        println!("event.wait_timeout(): {}", error);
+        assert!(error.is_timeout());
+        assert!(
+            queue
+                .finish_timeout(Duration::from_millis(10))
+                .unwrap_err()
+                .is_timeout()
+        );
+
+        user_event.complete().unwrap();
+        event.wait_timeout(Duration::from_secs(10)).unwrap();
+        queue.finish_timeout(Duration::MAX).unwrap();
+    }
+
+    #[test]
+    fn test_event_future() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new().build(&context).unwrap();
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_ONLY, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        let event = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &[])
+                .unwrap()
+        };
+
+        // Await the write
+        let future = event.into_future();
+        let event = future.event().clone();
+        block_on(future).unwrap();
+        assert_eq!(CL_COMPLETE, event.command_execution_status().unwrap().0);
+
+        // An event that has already completed is ready on the first poll
+        block_on(event.into_future()).unwrap();
+    }
+
+    #[cfg(any(feature = "CL_VERSION_2_1", feature = "dynamic"))]
+    #[test]
+    fn test_clock_correlation() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        let platform = &platforms[0];
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        let device = Device::new(devices[0]);
+        if !device.supports_version(2, 1) {
+            println!("OpenCL 2.1 device timers are not supported");
+            return;
+        }
+
+        let correlation =
+            ClockCorrelation::with_interval(&device, Duration::from_millis(10)).unwrap();
+        println!("ClockCorrelation offset: {}", correlation.offset());
+        println!("ClockCorrelation drift: {}", correlation.drift());
+        assert!(0.0 < correlation.drift());
+
+        let context = Context::from_device(&device).unwrap();
+        let queue = CommandQueueBuilder::new()
+            .set_profiling(true)
+            .build(&context)
+            .expect("CommandQueueBuilder::build failed");
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_ONLY, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        let before = Instant::now();
+        let event = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_NON_BLOCKING, 0, &ones, &[])
+                .unwrap()
+        };
+        event.wait().unwrap();
+        let after = Instant::now();
+
+        let (start, end) = correlation.event_instants(&event).unwrap();
+        println!(
+            "write started after: {:?}",
+            start.saturating_duration_since(before)
+        );
+        println!("write took: {:?}", end.saturating_duration_since(start));
+        assert!(start <= end);
+        // Allow for the accuracy of the clock correlation
+        let tolerance = Duration::from_millis(10);
+        assert!(before <= start + tolerance);
+        assert!(end <= after + tolerance);
+
+        let (start, end) = correlation.event_system_times(&event).unwrap();
+        assert!(start <= end);
+    }
 }
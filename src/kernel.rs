@@ -16,8 +16,10 @@ pub use cl3::kernel::*;
 
 use super::Result;
 use super::command_queue::CommandQueue;
-use super::event::Event;
+use super::context::Context;
+use super::event::{Event, EventList};
 use super::program::Program;
+use super::release::check_release;
 
 #[allow(unused_imports)]
 use cl3::ext;
@@ -56,7 +58,7 @@ impl Clone for Kernel {
 
 impl Drop for Kernel {
     fn drop(&mut self) {
-        unsafe { release_kernel(self.kernel).expect("Error: clReleaseKernel") };
+        check_release("clReleaseKernel", unsafe { release_kernel(self.kernel) });
     }
 }
 
@@ -74,11 +76,40 @@ impl Kernel {
         Self { kernel }
     }
 
+    /// Create a Kernel from an OpenCL cl_kernel that is owned elsewhere.  
+    /// Calls clRetainKernel, so that the Kernel can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `kernel` must be a valid OpenCL cl_kernel.
+    pub unsafe fn from_raw_retained(kernel: cl_kernel) -> Result<Self> {
+        unsafe { retain_kernel(kernel)? };
+        Ok(Self::new(kernel))
+    }
+
+    /// Retain the OpenCL kernel, so that both Kernels share it, including
+    /// its arguments.  
+    /// Note: [Kernel::clone] calls clCloneKernel to create a new kernel
+    /// object with a copy of the arguments.
+    ///
+    /// returns a Result containing the new Kernel
+    /// or the error code from the OpenCL C API function.
+    pub fn retain(&self) -> Result<Self> {
+        unsafe { Self::from_raw_retained(self.kernel) }
+    }
+
     /// Get the underlying OpenCL cl_kernel.
     pub const fn get(&self) -> cl_kernel {
         self.kernel
     }
 
+    /// Release the OpenCL kernel, returning any error from clReleaseKernel instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = mem::ManuallyDrop::new(self);
+        Ok(unsafe { release_kernel(this.kernel) }?)
+    }
+
     /// Create a Kernel from an OpenCL Program.
     ///
     /// * `program` - a built OpenCL Program.
@@ -194,6 +225,16 @@ impl Kernel {
         Ok(isize::from(get_kernel_info(self.kernel, CL_KERNEL_PROGRAM)?) as cl_program)
     }
 
+    /// The Context of the kernel, retained so that it can outlive the Kernel.
+    pub fn retained_context(&self) -> Result<Context> {
+        unsafe { Context::from_raw_retained(self.context()?) }
+    }
+
+    /// The Program of the kernel, retained so that it can outlive the Kernel.
+    pub fn retained_program(&self) -> Result<Program> {
+        unsafe { Program::from_raw_retained(self.program()?) }
+    }
+
     pub fn attributes(&self) -> Result<String> {
         Ok(get_kernel_info(self.kernel, CL_KERNEL_ATTRIBUTES)?.into())
     }
@@ -340,6 +381,8 @@ pub struct ExecuteKernel<'a> {
     pub event_wait_list: Vec<cl_event>,
 
     arg_index: cl_uint,
+    /// The events retained for event_wait_list until the kernel is enqueued.
+    wait_events: Vec<Event>,
 }
 
 impl<'a> ExecuteKernel<'a> {
@@ -355,6 +398,7 @@ impl<'a> ExecuteKernel<'a> {
             event_wait_list: Vec::new(),
 
             arg_index: 0,
+            wait_events: Vec::new(),
         }
     }
 
@@ -575,16 +619,44 @@ impl<'a> ExecuteKernel<'a> {
     }
 
     /// Set an event for the event_wait_list in a call to clEnqueueNDRangeKernel.  
+    /// Retains the event until the kernel is enqueued, so that it can be
+    /// dropped before then.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event cannot be retained.
     ///
     /// * `event` - the Event to add to the event_wait_list.
     ///
     /// returns a reference to self.
     pub fn set_wait_event<'b>(&'b mut self, event: &Event) -> &'b mut Self {
         self.event_wait_list.push(event.get());
+        self.wait_events.push(event.clone());
+        self
+    }
+
+    /// Set the events of an [EventList] for the event_wait_list in a call to
+    /// clEnqueueNDRangeKernel.  
+    /// Retains the events until the kernel is enqueued, so that the list can
+    /// be dropped before then.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an event cannot be retained.
+    ///
+    /// * `events` - the EventList to add to the event_wait_list.
+    ///
+    /// returns a reference to self.
+    pub fn set_event_list<'b>(&'b mut self, events: &EventList) -> &'b mut Self {
+        for event in events {
+            self.set_wait_event(event);
+        }
         self
     }
 
     /// Set the event_wait_list in a call to clEnqueueNDRangeKernel.  
+    /// The cl_events are not retained, so they must not be released until
+    /// the kernel has been enqueued, see [ExecuteKernel::set_event_list].
     ///
     /// # Panics
     ///
@@ -635,6 +707,7 @@ impl<'a> ExecuteKernel<'a> {
         self.global_work_sizes.clear();
         self.local_work_sizes.clear();
         self.event_wait_list.clear();
+        self.wait_events.clear();
 
         self.arg_index = 0;
     }
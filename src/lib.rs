@@ -94,6 +94,7 @@
 //! [Platform]: platform/struct.Platform.html
 //! [Device]: device/struct.Device.html
 //! [SubDevice]: device/struct.SubDevice.html
+//! [DeviceSelector]: device/struct.DeviceSelector.html
 //! [Context]: context/struct.Context.html
 //! [Program]: program/struct.Program.html
 //! [Kernel]: kernel/struct.Kernel.html
@@ -133,6 +134,9 @@
 //!
 //! The [Platform] and [Device] modules contain structures and methods to simplify
 //! querying the host system [Platform]s and [Device]s to create a [Context].
+//! A [DeviceSelector] filters and ranks the [Device]s on all [Platform]s by their
+//! type, OpenCL version, extensions, memory size, [svm] and floating point
+//! capabilities.
 //!
 //! ## Programming Model
 //!
@@ -200,6 +204,11 @@
 
 extern crate cl3;
 
+#[macro_use]
+mod macros;
+
+mod callback;
+
 #[cfg(any(feature = "cl_khr_command_buffer", feature = "dynamic"))]
 pub mod command_buffer;
 pub mod command_queue;
@@ -209,7 +218,9 @@ pub mod event;
 pub mod kernel;
 pub mod memory;
 pub mod platform;
+pub mod profiler;
 pub mod program;
+pub mod release;
 #[cfg(any(feature = "CL_VERSION_2_0", feature = "dynamic"))]
 pub mod svm;
 
@@ -0,0 +1,124 @@
+// Copyright (c) 2026 Via Technology Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Define a newtype for an OpenCL bit-field with a named constant for each
+/// flag, so that flags for different OpenCL parameters cannot be mixed up.
+///
+/// The newtype implements the bitwise operators, `contains`, conversions
+/// to and from the raw bit-field type and a `Debug` that prints the names of
+/// the flags that are set.
+macro_rules! cl_flags {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident($raw:ty) {
+            $(const $flag:ident = $value:expr;)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name($raw);
+
+        impl $name {
+            $(pub const $flag: Self = Self($value);)*
+
+            /// No flags set.
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Create from a raw bit-field.
+            pub const fn from_bits(bits: $raw) -> Self {
+                Self(bits)
+            }
+
+            /// The raw bit-field.
+            pub const fn bits(self) -> $raw {
+                self.0
+            }
+
+            /// Whether no flags are set.
+            pub const fn is_empty(self) -> bool {
+                0 == self.0
+            }
+
+            /// Whether all the flags in `other` are set.
+            pub const fn contains(self, other: Self) -> bool {
+                other.0 == self.0 & other.0
+            }
+
+            /// Whether any of the flags in `other` are set.
+            pub const fn intersects(self, other: Self) -> bool {
+                0 != self.0 & other.0
+            }
+        }
+
+        impl From<$raw> for $name {
+            fn from(bits: $raw) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl From<$name> for $raw {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl ::std::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl ::std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl ::std::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl ::std::ops::BitAndAssign for $name {
+            fn bitand_assign(&mut self, rhs: Self) {
+                self.0 &= rhs.0;
+            }
+        }
+
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let mut names = Vec::<String>::new();
+                let mut remaining = self.0;
+                $(
+                    // Only single bit flags, e.g. not CL_DEVICE_TYPE_ALL
+                    if 1 == $value.count_ones() && 0 != (self.0 & $value) {
+                        names.push(stringify!($flag).to_owned());
+                        remaining &= !$value;
+                    }
+                )*
+                if 0 != remaining || names.is_empty() {
+                    names.push(format!("{remaining:#x}"));
+                }
+                write!(f, "{}({})", stringify!($name), names.join(" | "))
+            }
+        }
+    };
+}
@@ -19,14 +19,19 @@ use core::marker::PhantomData;
 
 pub use cl3::memory::*;
 
+use super::command_queue::CommandQueue;
 use super::context::Context;
 
 use super::Result;
+use super::callback;
+use super::event::Event;
+use super::release::{check_release, release_error};
 #[cfg(any(feature = "cl_intel_dx9_media_sharing", feature = "dynamic"))]
 #[allow(unused_imports)]
 use cl3::dx9_media_sharing;
 #[allow(unused_imports)]
 use cl3::egl;
+use cl3::error_codes::{CL_INVALID_MEM_OBJECT, ClError};
 #[allow(unused_imports)]
 use cl3::ext;
 use cl3::gl;
@@ -35,11 +40,54 @@ use cl3::sampler;
 
 #[allow(unused_imports)]
 use cl3::types::{
-    cl_addressing_mode, cl_bool, cl_filter_mode, cl_sampler, cl_sampler_info, cl_sampler_properties,
+    cl_addressing_mode, cl_bool, cl_filter_mode, cl_map_flags, cl_sampler, cl_sampler_info,
+    cl_sampler_properties,
 };
 
 use libc::{c_void, intptr_t, size_t};
-use std::mem;
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+cl_flags! {
+    /// Allocation and usage information about a memory object, see:
+    /// [Memory Flags](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#memory-flags-table).
+    pub struct MemFlags(cl_mem_flags) {
+        const READ_WRITE = CL_MEM_READ_WRITE;
+        const WRITE_ONLY = CL_MEM_WRITE_ONLY;
+        const READ_ONLY = CL_MEM_READ_ONLY;
+        const USE_HOST_PTR = CL_MEM_USE_HOST_PTR;
+        const ALLOC_HOST_PTR = CL_MEM_ALLOC_HOST_PTR;
+        const COPY_HOST_PTR = CL_MEM_COPY_HOST_PTR;
+        const HOST_WRITE_ONLY = CL_MEM_HOST_WRITE_ONLY;
+        const HOST_READ_ONLY = CL_MEM_HOST_READ_ONLY;
+        const HOST_NO_ACCESS = CL_MEM_HOST_NO_ACCESS;
+        const KERNEL_READ_AND_WRITE = CL_MEM_KERNEL_READ_AND_WRITE;
+    }
+}
+
+cl_flags! {
+    /// Allocation and usage information about a Shared Virtual Memory (SVM)
+    /// buffer, see:
+    /// [SVM Memory Flags](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#svm-flags-table).
+    pub struct SvmMemFlags(cl_svm_mem_flags) {
+        const READ_WRITE = CL_MEM_READ_WRITE;
+        const WRITE_ONLY = CL_MEM_WRITE_ONLY;
+        const READ_ONLY = CL_MEM_READ_ONLY;
+        const SVM_FINE_GRAIN_BUFFER = CL_MEM_SVM_FINE_GRAIN_BUFFER;
+        const SVM_ATOMICS = CL_MEM_SVM_ATOMICS;
+    }
+}
+
+cl_flags! {
+    /// How a region of a memory object is mapped, see:
+    /// [Map Flags](https://www.khronos.org/registry/OpenCL/specs/3.0-unified/html/OpenCL_API.html#memory-map-flags-table).
+    pub struct MapFlags(cl_map_flags) {
+        const READ = CL_MAP_READ;
+        const WRITE = CL_MAP_WRITE;
+        const WRITE_INVALIDATE_REGION = CL_MAP_WRITE_INVALIDATE_REGION;
+    }
+}
 
 pub trait ClMem {
     fn get(&self) -> cl_mem;
@@ -81,6 +129,77 @@ pub trait ClMem {
         )?) as cl_mem)
     }
 
+    /// The Context of the memory object, retained so that it can outlive
+    /// the memory object.
+    fn retained_context(&self) -> Result<Context> {
+        unsafe { Context::from_raw_retained(self.context()?) }
+    }
+
+    /// The buffer that a sub-buffer or an image was created from, retained so
+    /// that it can outlive the memory object.
+    ///
+    /// returns a Result containing the Buffer, or None if the memory object
+    /// was not created from a buffer,
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// The associated buffer must contain T objects.
+    unsafe fn retained_associated_buffer<T>(&self) -> Result<Option<Buffer<T>>>
+    where
+        Self: Sized,
+    {
+        let mem = self.associated_memobject()?;
+        if mem.is_null() {
+            Ok(None)
+        } else {
+            unsafe { Buffer::from_raw_retained(mem).map(Some) }
+        }
+    }
+
+    /// The image that an image was created from, retained so that it can
+    /// outlive the memory object.
+    ///
+    /// returns a Result containing the Image, or None if the memory object
+    /// was not created from an image,
+    /// or the error code from the OpenCL C API function.
+    fn retained_associated_image(&self) -> Result<Option<Image>> {
+        let mem = self.associated_memobject()?;
+        let is_image = !mem.is_null()
+            && CL_MEM_OBJECT_BUFFER
+                != cl_mem_object_type::from(memory::get_mem_object_info(mem, CL_MEM_TYPE)?);
+        if is_image {
+            unsafe { Image::from_raw_retained(mem).map(Some) }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Call a closure when the memory object is destroyed, after its
+    /// resources have been released, e.g. to free a host_ptr used by the
+    /// memory object.  
+    /// The closure may be called on any thread, any panic in it is caught.
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    fn on_destroy<F>(&self, callback: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnOnce() + Send + 'static,
+    {
+        callback::register_once(
+            move |()| callback(),
+            |user_data| {
+                Ok(unsafe {
+                    memory::set_mem_object_destructor_callback(
+                        self.get(),
+                        mem_destructor_callback,
+                        user_data,
+                    )
+                }?)
+            },
+        )
+    }
+
     fn offset(&self) -> Result<size_t> {
         Ok(memory::get_mem_object_info(self.get(), CL_MEM_OFFSET)?.into())
     }
@@ -109,6 +228,11 @@ pub trait ClMem {
     }
 }
 
+/// The clSetMemObjectDestructorCallback pfn_notify function for [ClMem::on_destroy].
+extern "C" fn mem_destructor_callback(_memobj: cl_mem, user_data: *mut c_void) {
+    unsafe { callback::call_once(user_data, ()) };
+}
+
 /// An OpenCL buffer.
 ///
 /// Implements the Drop trait to call release_mem_object when the object is dropped.
@@ -137,7 +261,16 @@ impl<T> ClMem for Buffer<T> {
 
 impl<T> Drop for Buffer<T> {
     fn drop(&mut self) {
-        unsafe { memory::release_mem_object(self.get()).expect("Error: clReleaseMemObject") };
+        check_release("clReleaseMemObject", unsafe {
+            memory::release_mem_object(self.get())
+        });
+    }
+}
+
+impl<T> Clone for Buffer<T> {
+    /// Retain the OpenCL buffer, so that both Buffers share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.buffer) }.expect("Error: clRetainMemObject")
     }
 }
 
@@ -152,6 +285,29 @@ impl<T> Buffer<T> {
         }
     }
 
+    /// Create a Buffer from an OpenCL cl_mem that is owned elsewhere.  
+    /// Calls clRetainMemObject, so that the Buffer can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `buffer` must be a valid OpenCL buffer of T objects.
+    pub unsafe fn from_raw_retained(buffer: cl_mem) -> Result<Self> {
+        unsafe { memory::retain_mem_object(buffer)? };
+        Ok(Self::new(buffer))
+    }
+
+    /// The number of T objects in the buffer, from its size.
+    pub fn count(&self) -> Result<usize> {
+        Ok(self.size()? / mem::size_of::<T>())
+    }
+
+    /// Release the OpenCL buffer, returning any error from clReleaseMemObject instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { memory::release_mem_object(this.buffer) }?)
+    }
+
     /// Create a Buffer for a context.  
     ///
     /// * `context` - a valid OpenCL context.
@@ -166,13 +322,17 @@ impl<T> Buffer<T> {
     /// or the error code from the OpenCL C API function.
     pub unsafe fn create(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         count: size_t,
         host_ptr: *mut c_void,
     ) -> Result<Self> {
         unsafe {
-            let buffer =
-                memory::create_buffer(context.get(), flags, count * mem::size_of::<T>(), host_ptr)?;
+            let buffer = memory::create_buffer(
+                context.get(),
+                flags.into().bits(),
+                count * mem::size_of::<T>(),
+                host_ptr,
+            )?;
             Ok(Self::new(buffer))
         }
     }
@@ -196,7 +356,7 @@ impl<T> Buffer<T> {
     pub unsafe fn create_with_properties(
         context: &Context,
         properties: *const cl_mem_properties,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         count: size_t,
         host_ptr: *mut c_void,
     ) -> Result<Self> {
@@ -204,7 +364,7 @@ impl<T> Buffer<T> {
             let buffer = memory::create_buffer_with_properties(
                 context.get(),
                 properties,
-                flags,
+                flags.into().bits(),
                 count * mem::size_of::<T>(),
                 host_ptr,
             )?;
@@ -224,11 +384,11 @@ impl<T> Buffer<T> {
     /// or the error code from the OpenCL C API function.
     pub unsafe fn create_from_gl_buffer(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         bufobj: gl::cl_GLuint,
     ) -> Result<Self> {
         unsafe {
-            let buffer = gl::create_from_gl_buffer(context.get(), flags, bufobj)?;
+            let buffer = gl::create_from_gl_buffer(context.get(), flags.into().bits(), bufobj)?;
             Ok(Self::new(buffer))
         }
     }
@@ -240,7 +400,7 @@ impl<T> Buffer<T> {
     pub unsafe fn create_with_properties_intel(
         context: &Context,
         properties: *const ext::cl_mem_properties_intel,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         count: size_t,
         host_ptr: *mut c_void,
     ) -> Result<Self> {
@@ -248,7 +408,7 @@ impl<T> Buffer<T> {
             let buffer = ext::create_buffer_with_properties_intel(
                 context.get(),
                 properties,
-                flags,
+                flags.into().bits(),
                 count * mem::size_of::<T>(),
                 host_ptr,
             )?;
@@ -270,7 +430,7 @@ impl<T> Buffer<T> {
     /// or the error code from the OpenCL C API function.
     pub unsafe fn create_sub_buffer(
         &self,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         origin: usize,
         count: usize,
     ) -> Result<Self> {
@@ -281,7 +441,7 @@ impl<T> Buffer<T> {
             };
             let buffer = memory::create_sub_buffer(
                 self.buffer,
-                flags,
+                flags.into().bits(),
                 CL_BUFFER_CREATE_TYPE_REGION,
                 &buffer_create_info as *const _ as *const c_void,
             )?;
@@ -318,7 +478,16 @@ impl ClMem for Image {
 
 impl Drop for Image {
     fn drop(&mut self) {
-        unsafe { memory::release_mem_object(self.get()).expect("Error: clReleaseMemObject") };
+        check_release("clReleaseMemObject", unsafe {
+            memory::release_mem_object(self.get())
+        });
+    }
+}
+
+impl Clone for Image {
+    /// Retain the OpenCL image, so that both Images share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.image) }.expect("Error: clRetainMemObject")
     }
 }
 
@@ -329,6 +498,24 @@ impl Image {
         Self { image }
     }
 
+    /// Create an Image from an OpenCL cl_mem that is owned elsewhere.  
+    /// Calls clRetainMemObject, so that the Image can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `image` must be a valid OpenCL image.
+    pub unsafe fn from_raw_retained(image: cl_mem) -> Result<Self> {
+        unsafe { memory::retain_mem_object(image)? };
+        Ok(Self::new(image))
+    }
+
+    /// Release the OpenCL image, returning any error from clReleaseMemObject instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { memory::release_mem_object(this.image) }?)
+    }
+
     /// Create an OpenCL image object for a context.  
     ///
     /// * `context` - a valid OpenCL context.
@@ -347,14 +534,19 @@ impl Image {
     #[cfg(any(feature = "CL_VERSION_1_2", feature = "dynamic"))]
     pub unsafe fn create(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         image_format: *const cl_image_format,
         image_desc: *const cl_image_desc,
         host_ptr: *mut c_void,
     ) -> Result<Self> {
         unsafe {
-            let image =
-                memory::create_image(context.get(), flags, image_format, image_desc, host_ptr)?;
+            let image = memory::create_image(
+                context.get(),
+                flags.into().bits(),
+                image_format,
+                image_desc,
+                host_ptr,
+            )?;
             Ok(Self::new(image))
         }
     }
@@ -381,7 +573,7 @@ impl Image {
     pub unsafe fn create_with_properties(
         context: &Context,
         properties: *const cl_mem_properties,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         image_format: *const cl_image_format,
         image_desc: *const cl_image_desc,
         host_ptr: *mut c_void,
@@ -390,7 +582,7 @@ impl Image {
             let image = memory::create_image_with_properties(
                 context.get(),
                 properties,
-                flags,
+                flags.into().bits(),
                 image_format,
                 image_desc,
                 host_ptr,
@@ -417,7 +609,7 @@ impl Image {
     /// or the error code from the OpenCL C API function.
     pub unsafe fn create_from_gl_texture(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         texture_target: gl::cl_GLenum,
         miplevel: gl::cl_GLint,
         texture: gl::cl_GLuint,
@@ -425,7 +617,7 @@ impl Image {
         unsafe {
             let image = gl::create_from_gl_texture(
                 context.get(),
-                flags,
+                flags.into().bits(),
                 texture_target,
                 miplevel,
                 texture,
@@ -446,11 +638,12 @@ impl Image {
     /// or the error code from the OpenCL C API function.
     pub unsafe fn create_from_gl_render_buffer(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         renderbuffer: gl::cl_GLuint,
     ) -> Result<Self> {
         unsafe {
-            let image = gl::create_from_gl_render_buffer(context.get(), flags, renderbuffer)?;
+            let image =
+                gl::create_from_gl_render_buffer(context.get(), flags.into().bits(), renderbuffer)?;
             Ok(Self::new(image))
         }
     }
@@ -473,7 +666,7 @@ impl Image {
         context: &Context,
         display: egl::CLeglDisplayKHR,
         image: egl::CLeglImageKHR,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         properties: &[egl::cl_egl_image_properties_khr],
     ) -> Result<Self> {
         unsafe {
@@ -481,7 +674,7 @@ impl Image {
                 context.get(),
                 display,
                 image,
-                flags,
+                flags.into().bits(),
                 properties.as_ptr(),
             )?;
             Ok(Self::new(image))
@@ -492,7 +685,7 @@ impl Image {
     #[inline]
     pub unsafe fn create_from_dx9_media_surface_intel(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         resource: dx9_media_sharing::IDirect3DSurface9_ptr,
         shared_handle: dx9_media_sharing::HANDLE,
         plane: cl_uint,
@@ -500,7 +693,7 @@ impl Image {
         unsafe {
             let image = dx9_media_sharing::create_from_dx9_media_surface_intel(
                 context.get(),
-                flags,
+                flags.into().bits(),
                 resource,
                 shared_handle,
                 plane,
@@ -577,6 +770,547 @@ impl Image {
     pub fn get_gl_texture_data(&self, param_name: gl::cl_gl_texture_info) -> Result<Vec<u8>> {
         Ok(gl::get_gl_texture_data(self.image, param_name)?)
     }
+
+    /// The size of the image in pixels, with the number of images of an image
+    /// array in the unused dimension: (width, 1, 1) for a 1D image,
+    /// (width, array size, 1) for a 1D image array, (width, height, 1) for a
+    /// 2D image, (width, height, array size) for a 2D image array and
+    /// (width, height, depth) for a 3D image.
+    ///
+    /// returns a Result containing the Region3 of the image, CL_INVALID_MEM_OBJECT
+    /// if the memory object is not an image,
+    /// or the error code from the OpenCL C API function.
+    pub fn extent(&self) -> Result<Region3> {
+        let mem_type = self.mem_type()?;
+        if CL_MEM_OBJECT_IMAGE1D == mem_type || CL_MEM_OBJECT_IMAGE1D_BUFFER == mem_type {
+            Ok(Region3::new(self.width()?, 1, 1))
+        } else if CL_MEM_OBJECT_IMAGE1D_ARRAY == mem_type {
+            Ok(Region3::new(self.width()?, self.array_size()?, 1))
+        } else if CL_MEM_OBJECT_IMAGE2D == mem_type {
+            Ok(Region3::new(self.width()?, self.height()?, 1))
+        } else if CL_MEM_OBJECT_IMAGE2D_ARRAY == mem_type {
+            Ok(Region3::new(
+                self.width()?,
+                self.height()?,
+                self.array_size()?,
+            ))
+        } else if CL_MEM_OBJECT_IMAGE3D == mem_type {
+            Ok(Region3::new(self.width()?, self.height()?, self.depth()?))
+        } else {
+            Err(ClError(CL_INVALID_MEM_OBJECT))
+        }
+    }
+
+    /// Check that a region is not empty and is within the image, see [Image::extent].
+    pub(crate) fn check_region(&self, origin: Origin3, region: Region3) -> Result<()> {
+        let extent = self.extent()?;
+        let within = |offset: usize, size: usize, len: usize| {
+            offset.checked_add(size).is_some_and(|end| end <= len)
+        };
+        if !region.is_empty()
+            && within(origin.x, region.width, extent.width)
+            && within(origin.y, region.height, extent.height)
+            && within(origin.z, region.depth, extent.depth)
+        {
+            Ok(())
+        } else {
+            Err(ClError(CL_INVALID_VALUE))
+        }
+    }
+
+    /// Check that the host memory of `len` bytes holds an image region with
+    /// the pitches in bytes, resolving zero pitches to packed pitches.
+    ///
+    /// returns the pitches to pass to the OpenCL C API function, where the
+    /// slice pitch is zero for 1D and 2D images.
+    pub(crate) fn check_host_region(
+        &self,
+        region: Region3,
+        pitches: Pitches,
+        len: usize,
+    ) -> Result<Pitches> {
+        let mem_type = self.mem_type()?;
+        let row_len = region
+            .width
+            .checked_mul(self.element_size()?)
+            .ok_or(ClError(CL_INVALID_VALUE))?;
+
+        // The images of a 1D image array are separated by the slice pitch
+        let (slices, rows) = if CL_MEM_OBJECT_IMAGE1D_ARRAY == mem_type {
+            (region.height, 1)
+        } else {
+            (region.depth, region.height)
+        };
+        let has_slices = CL_MEM_OBJECT_IMAGE1D_ARRAY == mem_type
+            || CL_MEM_OBJECT_IMAGE2D_ARRAY == mem_type
+            || CL_MEM_OBJECT_IMAGE3D == mem_type;
+        if !has_slices && 0 != pitches.slice {
+            return Err(ClError(CL_INVALID_VALUE));
+        }
+
+        let layout = Region3::new(row_len, rows, slices);
+        let pitches = check_rect(Origin3::ZERO, layout, pitches, len)?;
+        Ok(if has_slices {
+            pitches
+        } else {
+            Pitches::new(pitches.row, 0)
+        })
+    }
+}
+
+/// The (x, y, z) origin of a rectangular region of a buffer or an image.
+///
+/// For a [Buffer], x is in T objects and y and z are in rows and slices.
+/// For an [Image], x, y and z are in pixels, except for image arrays where
+/// y is the image index of a 1D image array and z is the image index of a
+/// 2D image array.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Origin3 {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+impl Origin3 {
+    pub const ZERO: Self = Self::new(0, 0, 0);
+
+    pub const fn new(x: usize, y: usize, z: usize) -> Self {
+        Self { x, y, z }
+    }
+
+    pub const fn to_array(self) -> [usize; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// The origin for the OpenCL C API functions, with x in bytes for T
+    /// objects of `size` bytes.
+    pub(crate) const fn to_byte_array(self, size: usize) -> [usize; 3] {
+        [self.x * size, self.y, self.z]
+    }
+}
+
+impl From<[usize; 3]> for Origin3 {
+    fn from(value: [usize; 3]) -> Self {
+        Self::new(value[0], value[1], value[2])
+    }
+}
+
+impl From<Origin3> for [usize; 3] {
+    fn from(value: Origin3) -> Self {
+        value.to_array()
+    }
+}
+
+/// The (width, height, depth) of a rectangular region of a buffer or an image,
+/// in the units of [Origin3].  
+/// Unused dimensions must be 1, e.g. the height and depth of a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Region3 {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+impl Region3 {
+    pub const fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            width,
+            height,
+            depth,
+        }
+    }
+
+    /// A 1D region: (width, 1, 1).
+    pub const fn new_1d(width: usize) -> Self {
+        Self::new(width, 1, 1)
+    }
+
+    /// A 2D region: (width, height, 1).
+    pub const fn new_2d(width: usize, height: usize) -> Self {
+        Self::new(width, height, 1)
+    }
+
+    /// Whether any dimension of the region is zero.
+    pub const fn is_empty(self) -> bool {
+        0 == self.width || 0 == self.height || 0 == self.depth
+    }
+
+    pub const fn to_array(self) -> [usize; 3] {
+        [self.width, self.height, self.depth]
+    }
+
+    /// The region for the OpenCL C API functions, with the width in bytes
+    /// for T objects of `size` bytes.
+    pub(crate) const fn to_byte_array(self, size: usize) -> [usize; 3] {
+        [self.width * size, self.height, self.depth]
+    }
+}
+
+impl From<[usize; 3]> for Region3 {
+    fn from(value: [usize; 3]) -> Self {
+        Self::new(value[0], value[1], value[2])
+    }
+}
+
+impl From<Region3> for [usize; 3] {
+    fn from(value: Region3) -> Self {
+        value.to_array()
+    }
+}
+
+/// The row and slice pitches of a rectangular region in a buffer or in host memory.
+///
+/// The pitches are the distances between the starts of consecutive rows and
+/// of consecutive slices.  
+/// The pitches are in T objects for a [Buffer] and the host memory of
+/// buffer transfers, and in bytes for the host memory of image transfers.
+///
+/// A zero pitch is derived from the region, as in the OpenCL C API: the row
+/// pitch is the region width and the slice pitch is the region height times
+/// the row pitch, i.e. the region is packed. The default Pitches are both zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pitches {
+    pub row: usize,
+    pub slice: usize,
+}
+
+impl Pitches {
+    pub const fn new(row: usize, slice: usize) -> Self {
+        Self { row, slice }
+    }
+
+    /// The pitches of a packed region, in the units of the region width.
+    pub const fn packed(region: Region3) -> Self {
+        Self::new(region.width, region.width.saturating_mul(region.height))
+    }
+
+    /// The pitches with any zero pitch derived from the region.
+    pub const fn resolve(self, region: Region3) -> Self {
+        let row = if 0 == self.row {
+            region.width
+        } else {
+            self.row
+        };
+        let slice = if 0 == self.slice {
+            row.saturating_mul(region.height)
+        } else {
+            self.slice
+        };
+        Self::new(row, slice)
+    }
+
+    /// The pitches for the OpenCL C API functions, in bytes for T objects of
+    /// `size` bytes.
+    ///
+    /// returns the pitches in bytes, or CL_INVALID_VALUE if they overflow.
+    pub(crate) const fn to_bytes(self, size: usize) -> Result<Self> {
+        match (self.row.checked_mul(size), self.slice.checked_mul(size)) {
+            (Some(row), Some(slice)) => Ok(Self::new(row, slice)),
+            _ => Err(ClError(CL_INVALID_VALUE)),
+        }
+    }
+}
+
+/// The index after the last object of a region with resolved pitches,
+/// or None if it overflows.
+fn rect_end(origin: Origin3, region: Region3, pitches: Pitches) -> Option<usize> {
+    let slice = origin.z.checked_add(region.depth - 1)?;
+    let row = origin.y.checked_add(region.height - 1)?;
+    slice
+        .checked_mul(pitches.slice)?
+        .checked_add(row.checked_mul(pitches.row)?)?
+        .checked_add(origin.x.checked_add(region.width)?)
+}
+
+/// Check that a rectangular region is not empty and is within `len` objects,
+/// with the rows of the region within the row pitch from origin x and,
+/// if the region uses slices, i.e. it has a depth or z origin, the rows of
+/// the region within the slice pitch from origin y.  
+/// Otherwise the slice pitch must be at least the region height times the
+/// row pitch.
+///
+/// returns the pitches with any zero pitch derived from the region,
+/// or CL_INVALID_VALUE.
+pub(crate) fn check_rect(
+    origin: Origin3,
+    region: Region3,
+    pitches: Pitches,
+    len: usize,
+) -> Result<Pitches> {
+    if region.is_empty() {
+        return Err(ClError(CL_INVALID_VALUE));
+    }
+    let pitches = pitches.resolve(region);
+    let rows = if 1 < region.depth || 0 < origin.z {
+        origin.y.checked_add(region.height)
+    } else {
+        Some(region.height)
+    };
+    let valid_pitches = origin
+        .x
+        .checked_add(region.width)
+        .is_some_and(|end| end <= pitches.row)
+        && rows
+            .and_then(|rows| rows.checked_mul(pitches.row))
+            .is_some_and(|size| size <= pitches.slice);
+    match rect_end(origin, region, pitches) {
+        Some(end) if valid_pitches && end <= len => Ok(pitches),
+        _ => Err(ClError(CL_INVALID_VALUE)),
+    }
+}
+
+/// A region of a memory object mapped into the host address space.
+/// Implements the Drop trait to enqueue the unmap of the region.
+#[derive(Debug)]
+pub(crate) struct Mapping<'a> {
+    queue: &'a CommandQueue,
+    mem: cl_mem,
+    ptr: *mut c_void,
+}
+
+impl<'a> Mapping<'a> {
+    /// # Safety
+    ///
+    /// `ptr` must have been mapped from `mem` on `queue` and the mapping must
+    /// have completed.
+    pub(crate) const unsafe fn new(queue: &'a CommandQueue, mem: cl_mem, ptr: *mut c_void) -> Self {
+        Self { queue, mem, ptr }
+    }
+
+    fn unmap(self) -> Result<Event> {
+        let this = ManuallyDrop::new(self);
+        unsafe { this.queue.enqueue_unmap_mem_object(this.mem, this.ptr, &[]) }
+    }
+}
+
+impl Drop for Mapping<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = unsafe { self.queue.enqueue_unmap_mem_object(self.mem, self.ptr, &[]) } {
+            release_error("clEnqueueUnmapMemObject", e);
+        }
+    }
+}
+
+/// Check that a mapped pointer can be dereferenced as T objects.
+fn check_mapped_ptr<T>(mapping: &Mapping<'_>) -> Result<()> {
+    if mapping.ptr.is_null() || 0 != mapping.ptr.align_offset(mem::align_of::<T>()) {
+        Err(ClError(CL_INVALID_VALUE))
+    } else {
+        Ok(())
+    }
+}
+
+/// T objects of a buffer mapped for reading, see [CommandQueue::map_buffer].
+///
+/// Derefs to a slice of the mapped T objects.  
+/// Implements the Drop trait to enqueue the unmap of the T objects.
+#[derive(Debug)]
+pub struct MappedBuffer<'a, T> {
+    mapping: Mapping<'a>,
+    len: usize,
+    _buffer: PhantomData<&'a Buffer<T>>,
+}
+
+impl<'a, T> MappedBuffer<'a, T> {
+    /// # Safety
+    ///
+    /// `mapping` must map `len` T objects of a buffer for reading.
+    pub(crate) unsafe fn new(mapping: Mapping<'a>, len: usize) -> Result<Self> {
+        check_mapped_ptr::<T>(&mapping)?;
+        Ok(Self {
+            mapping,
+            len,
+            _buffer: PhantomData,
+        })
+    }
+
+    /// Enqueue the unmap of the T objects, instead of dropping the MappedBuffer.
+    ///
+    /// returns a Result containing the unmap Event
+    /// or the error code from the OpenCL C API function.
+    pub fn unmap(self) -> Result<Event> {
+        self.mapping.unmap()
+    }
+}
+
+impl<T> Deref for MappedBuffer<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.mapping.ptr as *const T, self.len) }
+    }
+}
+
+/// T objects of a buffer mapped for writing, see [CommandQueue::map_buffer_mut].
+///
+/// Derefs to a mutable slice of the mapped T objects.  
+/// Implements the Drop trait to enqueue the unmap of the T objects.
+#[derive(Debug)]
+pub struct MappedBufferMut<'a, T> {
+    mapping: Mapping<'a>,
+    len: usize,
+    _buffer: PhantomData<&'a mut Buffer<T>>,
+}
+
+impl<'a, T> MappedBufferMut<'a, T> {
+    /// # Safety
+    ///
+    /// `mapping` must map `len` T objects of a buffer for writing.
+    pub(crate) unsafe fn new(mapping: Mapping<'a>, len: usize) -> Result<Self> {
+        check_mapped_ptr::<T>(&mapping)?;
+        Ok(Self {
+            mapping,
+            len,
+            _buffer: PhantomData,
+        })
+    }
+
+    /// Enqueue the unmap of the T objects, instead of dropping the MappedBufferMut.
+    ///
+    /// returns a Result containing the unmap Event
+    /// or the error code from the OpenCL C API function.
+    pub fn unmap(self) -> Result<Event> {
+        self.mapping.unmap()
+    }
+}
+
+impl<T> Deref for MappedBufferMut<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.mapping.ptr as *const T, self.len) }
+    }
+}
+
+impl<T> DerefMut for MappedBufferMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.mapping.ptr as *mut T, self.len) }
+    }
+}
+
+/// The layout of a mapped image region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MappedImageLayout {
+    /// The number of bytes from the start of the region to its end.
+    pub(crate) len: usize,
+    pub(crate) row_pitch: usize,
+    pub(crate) slice_pitch: usize,
+}
+
+/// A region of an image mapped for reading, see [CommandQueue::map_image].
+///
+/// Derefs to the bytes of the mapped region, where a row starts every
+/// [MappedImage::row_pitch] bytes and a 2D slice (or a 1D image of an array)
+/// starts every [MappedImage::slice_pitch] bytes.  
+/// Implements the Drop trait to enqueue the unmap of the region.
+#[derive(Debug)]
+pub struct MappedImage<'a> {
+    mapping: Mapping<'a>,
+    layout: MappedImageLayout,
+    _image: PhantomData<&'a Image>,
+}
+
+impl<'a> MappedImage<'a> {
+    /// # Safety
+    ///
+    /// `mapping` must map an image region of `layout` for reading.
+    pub(crate) unsafe fn new(mapping: Mapping<'a>, layout: MappedImageLayout) -> Result<Self> {
+        check_mapped_ptr::<u8>(&mapping)?;
+        Ok(Self {
+            mapping,
+            layout,
+            _image: PhantomData,
+        })
+    }
+
+    /// The number of bytes between the starts of consecutive rows.
+    pub const fn row_pitch(&self) -> usize {
+        self.layout.row_pitch
+    }
+
+    /// The number of bytes between the starts of consecutive 2D slices of a
+    /// 3D image or a 2D image array, or images of a 1D image array,
+    /// 0 for other images.
+    pub const fn slice_pitch(&self) -> usize {
+        self.layout.slice_pitch
+    }
+
+    /// Enqueue the unmap of the region, instead of dropping the MappedImage.
+    ///
+    /// returns a Result containing the unmap Event
+    /// or the error code from the OpenCL C API function.
+    pub fn unmap(self) -> Result<Event> {
+        self.mapping.unmap()
+    }
+}
+
+impl Deref for MappedImage<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.mapping.ptr as *const u8, self.layout.len) }
+    }
+}
+
+/// A region of an image mapped for writing, see [CommandQueue::map_image_mut].
+///
+/// Derefs to the bytes of the mapped region, where a row starts every
+/// [MappedImageMut::row_pitch] bytes and a 2D slice (or a 1D image of an array)
+/// starts every [MappedImageMut::slice_pitch] bytes.  
+/// Implements the Drop trait to enqueue the unmap of the region.
+#[derive(Debug)]
+pub struct MappedImageMut<'a> {
+    mapping: Mapping<'a>,
+    layout: MappedImageLayout,
+    _image: PhantomData<&'a mut Image>,
+}
+
+impl<'a> MappedImageMut<'a> {
+    /// # Safety
+    ///
+    /// `mapping` must map an image region of `layout` for writing.
+    pub(crate) unsafe fn new(mapping: Mapping<'a>, layout: MappedImageLayout) -> Result<Self> {
+        check_mapped_ptr::<u8>(&mapping)?;
+        Ok(Self {
+            mapping,
+            layout,
+            _image: PhantomData,
+        })
+    }
+
+    /// The number of bytes between the starts of consecutive rows.
+    pub const fn row_pitch(&self) -> usize {
+        self.layout.row_pitch
+    }
+
+    /// The number of bytes between the starts of consecutive 2D slices of a
+    /// 3D image or a 2D image array, or images of a 1D image array,
+    /// 0 for other images.
+    pub const fn slice_pitch(&self) -> usize {
+        self.layout.slice_pitch
+    }
+
+    /// Enqueue the unmap of the region, instead of dropping the MappedImageMut.
+    ///
+    /// returns a Result containing the unmap Event
+    /// or the error code from the OpenCL C API function.
+    pub fn unmap(self) -> Result<Event> {
+        self.mapping.unmap()
+    }
+}
+
+impl Deref for MappedImageMut<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.mapping.ptr as *const u8, self.layout.len) }
+    }
+}
+
+impl DerefMut for MappedImageMut<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.mapping.ptr as *mut u8, self.layout.len) }
+    }
 }
 
 /// An OpenCL sampler.  
@@ -596,7 +1330,16 @@ impl From<Sampler> for cl_sampler {
 
 impl Drop for Sampler {
     fn drop(&mut self) {
-        unsafe { sampler::release_sampler(self.sampler).expect("Error: clReleaseSampler") };
+        check_release("clReleaseSampler", unsafe {
+            sampler::release_sampler(self.sampler)
+        });
+    }
+}
+
+impl Clone for Sampler {
+    /// Retain the OpenCL sampler, so that both Samplers share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.sampler) }.expect("Error: clRetainSampler")
     }
 }
 
@@ -607,6 +1350,24 @@ impl Sampler {
         Self { sampler }
     }
 
+    /// Create a Sampler from an OpenCL cl_sampler that is owned elsewhere.  
+    /// Calls clRetainSampler, so that the Sampler can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `sampler` must be a valid OpenCL sampler.
+    pub unsafe fn from_raw_retained(sampler: cl_sampler) -> Result<Self> {
+        unsafe { sampler::retain_sampler(sampler)? };
+        Ok(Self::new(sampler))
+    }
+
+    /// Release the OpenCL sampler, returning any error from clReleaseSampler instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { sampler::release_sampler(this.sampler) }?)
+    }
+
     #[cfg_attr(
         any(
             feature = "CL_VERSION_2_0",
@@ -658,6 +1419,11 @@ impl Sampler {
         )?) as cl_context)
     }
 
+    /// The Context of the sampler, retained so that it can outlive the Sampler.
+    pub fn retained_context(&self) -> Result<Context> {
+        unsafe { Context::from_raw_retained(self.context()?) }
+    }
+
     pub fn normalized_coords(&self) -> Result<bool> {
         Ok(cl_uint::from(sampler::get_sampler_info(
             self.get(),
@@ -722,7 +1488,17 @@ impl ClMem for Pipe {
 #[cfg(any(feature = "CL_VERSION_2_0", feature = "dynamic"))]
 impl Drop for Pipe {
     fn drop(&mut self) {
-        unsafe { memory::release_mem_object(self.get()).expect("Error: clReleaseMemObject") };
+        check_release("clReleaseMemObject", unsafe {
+            memory::release_mem_object(self.get())
+        });
+    }
+}
+
+#[cfg(any(feature = "CL_VERSION_2_0", feature = "dynamic"))]
+impl Clone for Pipe {
+    /// Retain the OpenCL pipe, so that both Pipes share it.
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw_retained(self.pipe) }.expect("Error: clRetainMemObject")
     }
 }
 
@@ -732,15 +1508,37 @@ impl Pipe {
         Self { pipe }
     }
 
+    /// Create a Pipe from an OpenCL cl_mem that is owned elsewhere.  
+    /// Calls clRetainMemObject, so that the Pipe can release it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `pipe` must be a valid OpenCL pipe.
+    pub unsafe fn from_raw_retained(pipe: cl_mem) -> Result<Self> {
+        unsafe { memory::retain_mem_object(pipe)? };
+        Ok(Self::new(pipe))
+    }
+
+    /// Release the OpenCL pipe, returning any error from clReleaseMemObject instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { memory::release_mem_object(this.pipe) }?)
+    }
+
     pub unsafe fn create(
         context: &Context,
-        flags: cl_mem_flags,
+        flags: impl Into<MemFlags>,
         pipe_packet_size: cl_uint,
         pipe_max_packets: cl_uint,
     ) -> Result<Self> {
         unsafe {
-            let pipe =
-                memory::create_pipe(context.get(), flags, pipe_packet_size, pipe_max_packets)?;
+            let pipe = memory::create_pipe(
+                context.get(),
+                flags.into().bits(),
+                pipe_packet_size,
+                pipe_max_packets,
+            )?;
             Ok(Self::new(pipe))
         }
     }
@@ -772,6 +1570,8 @@ mod tests {
     use crate::platform::get_platforms;
     use crate::types::cl_float;
     use std::ptr;
+    use std::sync::mpsc;
+    use std::time::Duration;
 
     #[test]
     fn test_memory_buffer() {
@@ -841,5 +1641,136 @@ mod tests {
             }
             Err(e) => println!("OpenCL error, CL_MEM_PROPERTIES: {:?}, {}", e, e),
         }
+
+        let (sender, receiver) = mpsc::channel();
+        buffer.on_destroy(move || sender.send(()).unwrap()).unwrap();
+        drop(buffer);
+        receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+    }
+
+    #[test]
+    fn test_mem_flags() {
+        let flags = MemFlags::READ_ONLY | MemFlags::COPY_HOST_PTR;
+        assert!(flags.contains(MemFlags::READ_ONLY));
+        assert!(!flags.contains(MemFlags::READ_ONLY | MemFlags::HOST_NO_ACCESS));
+        assert!(flags.intersects(MemFlags::READ_ONLY | MemFlags::HOST_NO_ACCESS));
+        assert_eq!(CL_MEM_READ_ONLY | CL_MEM_COPY_HOST_PTR, flags.bits());
+        assert_eq!(
+            flags,
+            MemFlags::from(CL_MEM_READ_ONLY | CL_MEM_COPY_HOST_PTR)
+        );
+        assert_eq!(
+            "MemFlags(READ_ONLY | COPY_HOST_PTR)",
+            format!("{:?}", flags)
+        );
+        assert_eq!("MemFlags(0x0)", format!("{:?}", MemFlags::empty()));
+        assert_eq!(
+            "MapFlags(WRITE | 0x8)",
+            format!("{:?}", MapFlags::WRITE | MapFlags::from(0x8))
+        );
+
+        let mut svm_flags = SvmMemFlags::READ_WRITE;
+        svm_flags |= SvmMemFlags::SVM_FINE_GRAIN_BUFFER;
+        assert_eq!(
+            "SvmMemFlags(READ_WRITE | SVM_FINE_GRAIN_BUFFER)",
+            format!("{:?}", svm_flags)
+        );
+    }
+
+    #[test]
+    fn test_check_rect() {
+        // A 3x2 tile at (1, 1) of a 4x4 grid, packed on the host
+        let origin = Origin3::new(1, 1, 0);
+        let region = Region3::new_2d(3, 2);
+        assert_eq!(
+            Pitches::new(4, 8),
+            check_rect(origin, region, Pitches::new(4, 0), 16).unwrap()
+        );
+        assert_eq!(
+            Pitches::packed(region),
+            check_rect(Origin3::ZERO, region, Pitches::default(), 6).unwrap()
+        );
+
+        // The last row ends at index 12 of the grid, not within 11 objects
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(origin, region, Pitches::new(4, 0), 11)
+                .unwrap_err()
+                .0
+        );
+        // A row pitch less than the region width
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(origin, region, Pitches::new(2, 0), 16)
+                .unwrap_err()
+                .0
+        );
+        // A slice pitch less than the rows of the region
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(origin, region, Pitches::new(4, 4), 16)
+                .unwrap_err()
+                .0
+        );
+        // Empty and overflowing regions
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(origin, Region3::new(3, 0, 1), Pitches::default(), 16)
+                .unwrap_err()
+                .0
+        );
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(
+                Origin3::new(0, 0, usize::MAX),
+                region,
+                Pitches::default(),
+                16
+            )
+            .unwrap_err()
+            .0
+        );
+
+        // A row that extends past the row pitch
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(Origin3::new(2, 0, 0), region, Pitches::new(4, 0), 16)
+                .unwrap_err()
+                .0
+        );
+        // Rows that extend past the slice pitch of a 3D region
+        assert_eq!(
+            CL_INVALID_VALUE,
+            check_rect(
+                Origin3::new(0, 1, 0),
+                Region3::new(3, 2, 2),
+                Pitches::new(4, 8),
+                32
+            )
+            .unwrap_err()
+            .0
+        );
+        assert_eq!(
+            Pitches::new(4, 12),
+            check_rect(
+                Origin3::new(0, 1, 0),
+                Region3::new(3, 2, 2),
+                Pitches::new(4, 12),
+                32
+            )
+            .unwrap()
+        );
+        // Pitches that overflow in bytes
+        assert_eq!(
+            CL_INVALID_VALUE,
+            Pitches::new(4, usize::MAX / 2).to_bytes(4).unwrap_err().0
+        );
+        assert_eq!(
+            Pitches::new(16, 32),
+            Pitches::new(4, 8).to_bytes(4).unwrap()
+        );
+
+        assert_eq!(Origin3::new(1, 2, 3), Origin3::from([1, 2, 3]));
+        assert_eq!([8, 2, 1], Region3::new_2d(2, 2).to_byte_array(4));
     }
 }
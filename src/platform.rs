@@ -17,9 +17,11 @@
 pub use cl3::platform;
 
 use super::Result;
+use super::device::{DeviceType, Extensions, NameVersion, OpenClVersion, name_versions};
 use cl3::device;
 #[allow(unused_imports)]
 use cl3::dx9_media_sharing;
+use cl3::error_codes::{CL_INVALID_VALUE, ClError};
 #[allow(unused_imports)]
 use cl3::ext;
 #[allow(unused_imports)]
@@ -83,8 +85,11 @@ impl Platform {
     /// println!("CL_DEVICE_TYPE_GPU count: {}", device_ids.len());
     /// assert!(0 < device_ids.len());
     /// ```
-    pub fn get_devices(&self, device_type: cl_device_type) -> Result<Vec<cl_device_id>> {
-        Ok(device::get_device_ids(self.id(), device_type)?)
+    pub fn get_devices(&self, device_type: impl Into<DeviceType>) -> Result<Vec<cl_device_id>> {
+        Ok(device::get_device_ids(
+            self.id(),
+            device_type.into().bits(),
+        )?)
     }
 
     #[cfg(any(feature = "cl_khr_dx9_media_sharing", feature = "dynamic"))]
@@ -116,6 +121,22 @@ impl Platform {
         Ok(platform::get_platform_info(self.id(), platform::CL_PLATFORM_VERSION)?.into())
     }
 
+    /// The OpenCL version supported by the Platform, parsed from [Platform::version].
+    ///
+    /// returns a Result containing the version, or CL_INVALID_VALUE if the
+    /// version string could not be parsed, or the error code from the OpenCL
+    /// C API function.
+    pub fn opencl_version(&self) -> Result<OpenClVersion> {
+        OpenClVersion::parse(&self.version()?).ok_or(ClError(CL_INVALID_VALUE))
+    }
+
+    /// Determine if the Platform supports OpenCL version major.minor or later.  
+    /// Returns true if the Platform supports it, false otherwise.
+    pub fn supports_version(&self, major: cl_uint, minor: cl_uint) -> bool {
+        self.opencl_version()
+            .is_ok_and(|version| version.is_at_least(major, minor))
+    }
+
     /// The OpenCL Platform name string.  
     pub fn name(&self) -> Result<String> {
         Ok(platform::get_platform_info(self.id(), platform::CL_PLATFORM_NAME)?.into())
@@ -131,6 +152,16 @@ impl Platform {
         Ok(platform::get_platform_info(self.id(), platform::CL_PLATFORM_EXTENSIONS)?.into())
     }
 
+    /// The set of extensions supported by the Platform.  
+    /// Uses [Platform::extensions_with_version] if the Platform supports it
+    /// (CL_VERSION_3_0), otherwise parses [Platform::extensions].
+    pub fn extension_set(&self) -> Result<Extensions> {
+        self.extensions_with_version().map_or_else(
+            |_| Ok(Extensions::parse(&self.extensions()?)),
+            |values| Ok(Extensions::from_name_versions(&values)),
+        )
+    }
+
     /// The resolution of the host timer in nanoseconds as used by
     /// clGetDeviceAndHostTimer.  
     /// CL_VERSION_2_1
@@ -205,6 +236,40 @@ impl Platform {
     pub unsafe fn unload_compiler(&self) -> Result<()> {
         unsafe { Ok(program::unload_platform_compiler(self.id())?) }
     }
+
+    /// Query all the information about the Platform.
+    /// Queries that the Platform does not support are set to None.
+    ///
+    /// returns a [PlatformInfo] snapshot of the Platform.
+    pub fn info(&self) -> PlatformInfo {
+        PlatformInfo {
+            profile: self.profile().ok(),
+            version: self.version().ok(),
+            name: self.name().ok(),
+            vendor: self.vendor().ok(),
+            extensions: self.extensions().ok(),
+            host_timer_resolution: self.host_timer_resolution().ok(),
+            numeric_version: self.numeric_version().ok(),
+            extensions_with_version: name_versions(self.extensions_with_version()),
+        }
+    }
+}
+
+/// A snapshot of the information about an OpenCL platform, see [Platform::info].
+///
+/// Each field is named after the [Platform] method used to query it and is
+/// None if the Platform does not support the query.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlatformInfo {
+    pub profile: Option<String>,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub vendor: Option<String>,
+    pub extensions: Option<String>,
+    pub host_timer_resolution: Option<cl_ulong>,
+    pub numeric_version: Option<cl_version>,
+    pub extensions_with_version: Option<Vec<NameVersion>>,
 }
 
 /// Get the available OpenCL platforms.  
@@ -252,6 +317,13 @@ mod tests {
 
             let value = platform.version().unwrap();
             println!("CL_PLATFORM_VERSION: {:?}", value);
+            let info = platform.info();
+            assert_eq!(platform.name().unwrap(), info.name.clone().unwrap());
+            println!("PlatformInfo: {:?}", info);
+
+            let version = platform.opencl_version().unwrap();
+            println!("OpenCL version: {}", version);
+            assert!(platform.supports_version(1, 2));
 
             println!("CL_PLATFORM_VENDOR: {}", platform.vendor().unwrap());
             println!(
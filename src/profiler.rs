@@ -0,0 +1,433 @@
+// Copyright (c) 2026 Via Technology Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A profiler for the commands of OpenCL command-queues.
+//!
+//! A [Profiler] attached to a [CommandQueue](crate::command_queue::CommandQueue)
+//! created with CL_QUEUE_PROFILING_ENABLE, see
+//! [CommandQueue::set_profiler](crate::command_queue::CommandQueue::set_profiler),
+//! records the events of the commands enqueued on the command-queue,
+//! including the kernels enqueued by
+//! [ExecuteKernel::enqueue_nd_range](crate::kernel::ExecuteKernel::enqueue_nd_range).
+//! Other events can be recorded with [Profiler::record].
+//!
+//! The recorded commands can be exported as a [Chrome Trace Event](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+//! timeline, see [Profiler::chrome_trace], or summarised per label, see
+//! [Profiler::summary].
+
+use super::Result;
+use super::event::{CL_COMPLETE, Event, EventCommandType};
+use super::types::{cl_command_queue, cl_command_type, cl_ulong};
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// A recorded command.
+#[derive(Debug)]
+struct Record {
+    event: Event,
+    label: Option<String>,
+    bytes: Option<usize>,
+}
+
+/// Records the events of OpenCL commands with labels, e.g. kernel function
+/// names, to export their profiling information.
+///
+/// A Profiler can be cloned to attach it to several command-queues,
+/// the clones share their records.
+/// The events of the commands are retained until the records are cleared.
+#[derive(Clone, Debug, Default)]
+pub struct Profiler {
+    records: Arc<Mutex<Vec<Record>>>,
+}
+
+/// The profiling information of a completed command, see [Profiler::timeline].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimelineEntry {
+    /// The label of the command, e.g. a kernel function name, or its command
+    /// type, e.g. CL_COMMAND_MARKER, with the size of a transfer,
+    /// e.g. "CL_COMMAND_READ_BUFFER 4096 bytes".
+    pub label: String,
+    pub command_type: cl_command_type,
+    pub command_queue: cl_command_queue,
+    /// The number of bytes transferred by a buffer or SVM command, if known.
+    pub bytes: Option<usize>,
+    /// The device time counters in nanoseconds, see
+    /// [Event::profiling_command_queued] etc.
+    pub queued: cl_ulong,
+    pub submit: cl_ulong,
+    pub start: cl_ulong,
+    pub end: cl_ulong,
+}
+
+impl TimelineEntry {
+    /// The time that the command took to execute.
+    pub const fn duration(&self) -> Duration {
+        Duration::from_nanos(self.end.saturating_sub(self.start))
+    }
+}
+
+/// The execution times of the commands with a label, see [Profiler::summary].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabelSummary {
+    pub label: String,
+    pub count: usize,
+    pub total: Duration,
+    pub mean: Duration,
+    /// The median execution time.
+    pub p50: Duration,
+    /// The 99th percentile execution time.
+    pub p99: Duration,
+}
+
+impl fmt::Display for LabelSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: count: {}, total: {:?}, mean: {:?}, p50: {:?}, p99: {:?}",
+            self.label, self.count, self.total, self.mean, self.p50, self.p99
+        )
+    }
+}
+
+/// The nearest rank percentile of sorted durations.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        Duration::ZERO
+    } else {
+        let rank = (percent * sorted.len()).div_ceil(100);
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+/// Write a string as a JSON string.
+fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+impl Profiler {
+    /// Create a Profiler without any records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Record>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record the event of a command with a label.
+    /// The event's command-queue must have been created with
+    /// CL_QUEUE_PROFILING_ENABLE.
+    ///
+    /// * `event` - the event of the command, retained by the Profiler.
+    /// * `label` - the label of the command, e.g. "upload weights".
+    ///
+    /// returns an empty Result or the error code from the OpenCL C API function.
+    pub fn record(&self, event: &Event, label: &str) -> Result<()> {
+        self.record_event(event, Some(label.to_owned()), None)
+    }
+
+    pub(crate) fn record_event(
+        &self,
+        event: &Event,
+        label: Option<String>,
+        bytes: Option<usize>,
+    ) -> Result<()> {
+        let event = unsafe { Event::from_raw_retained(event.get())? };
+        self.lock().push(Record {
+            event,
+            label,
+            bytes,
+        });
+        Ok(())
+    }
+
+    /// The number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove the records, releasing their events.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// The profiling information of the recorded commands, in the order that
+    /// they were recorded.
+    /// Waits for the commands to complete, commands that were terminated
+    /// are omitted.
+    ///
+    /// returns a Result containing the TimelineEntrys
+    /// or the error code from the OpenCL C API function.
+    pub fn timeline(&self) -> Result<Vec<TimelineEntry>> {
+        // Wait without the lock, so that commands can be recorded meanwhile
+        let records = self
+            .lock()
+            .iter()
+            .map(|record| {
+                let event = unsafe { Event::from_raw_retained(record.event.get())? };
+                Ok((event, record.label.clone(), record.bytes))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut timeline = Vec::with_capacity(records.len());
+        for (event, label, bytes) in records {
+            // An error is returned if the command was terminated
+            if event.wait().is_err() || CL_COMPLETE != event.command_execution_status()?.0 {
+                continue;
+            }
+
+            let command_type = event.command_type()?;
+            let label = label.unwrap_or_else(|| {
+                bytes.map_or_else(
+                    || command_type.to_string(),
+                    |bytes| format!("{command_type} {bytes} bytes"),
+                )
+            });
+            timeline.push(TimelineEntry {
+                label,
+                command_type: command_type.0,
+                command_queue: event.command_queue()?,
+                bytes,
+                queued: event.profiling_command_queued()?,
+                submit: event.profiling_command_submit()?,
+                start: event.profiling_command_start()?,
+                end: event.profiling_command_end()?,
+            });
+        }
+        Ok(timeline)
+    }
+
+    /// The recorded commands as Chrome Trace Event JSON, which can be viewed in
+    /// chrome://tracing or Perfetto.
+    /// Each command is a complete event with its execution time, in the
+    /// category of its command type, on a thread per command-queue.
+    /// The times are in microseconds from the start of the first command.
+    ///
+    /// returns a Result containing the JSON
+    /// or the error code from the OpenCL C API function.
+    pub fn chrome_trace(&self) -> Result<String> {
+        let timeline = self.timeline()?;
+        let origin = timeline.iter().map(|entry| entry.start).min().unwrap_or(0);
+        let mut queues: Vec<cl_command_queue> = Vec::new();
+
+        let mut json = String::from("{\"traceEvents\":[");
+        for (index, entry) in timeline.iter().enumerate() {
+            let tid = queues
+                .iter()
+                .position(|queue| *queue == entry.command_queue)
+                .unwrap_or_else(|| {
+                    queues.push(entry.command_queue);
+                    queues.len() - 1
+                });
+
+            if 0 < index {
+                json.push(',');
+            }
+            json.push_str("{\"name\":");
+            write_json_string(&mut json, &entry.label);
+            json.push_str(",\"cat\":");
+            write_json_string(&mut json, &EventCommandType(entry.command_type).to_string());
+            let _ = write!(
+                json,
+                ",\"ph\":\"X\",\"pid\":0,\"tid\":{},\"ts\":{:.3},\"dur\":{:.3}",
+                tid,
+                entry.start.saturating_sub(origin) as f64 / 1000.0,
+                entry.end.saturating_sub(entry.start) as f64 / 1000.0
+            );
+            let _ = write!(
+                json,
+                ",\"args\":{{\"queued\":{},\"submit\":{},\"start\":{},\"end\":{}",
+                entry.queued, entry.submit, entry.start, entry.end
+            );
+            if let Some(bytes) = entry.bytes {
+                let _ = write!(json, ",\"bytes\":{bytes}");
+            }
+            json.push_str("}}");
+        }
+        json.push_str("],\"displayTimeUnit\":\"ns\"}");
+        Ok(json)
+    }
+
+    /// The execution times of the recorded commands per label, in decreasing
+    /// order of total execution time.
+    ///
+    /// returns a Result containing the LabelSummarys
+    /// or the error code from the OpenCL C API function.
+    pub fn summary(&self) -> Result<Vec<LabelSummary>> {
+        let mut durations: HashMap<String, Vec<Duration>> = HashMap::new();
+        for entry in self.timeline()? {
+            let duration = entry.duration();
+            durations.entry(entry.label).or_default().push(duration);
+        }
+
+        let mut summary: Vec<LabelSummary> = durations
+            .into_iter()
+            .map(|(label, mut durations)| {
+                durations.sort_unstable();
+                let count = durations.len();
+                let total: Duration = durations.iter().sum();
+                LabelSummary {
+                    label,
+                    count,
+                    total,
+                    mean: total / count as u32,
+                    p50: percentile(&durations, 50),
+                    p99: percentile(&durations, 99),
+                }
+            })
+            .collect();
+        summary.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.label.cmp(&b.label)));
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_queue::CommandQueueBuilder;
+    use crate::context::Context;
+    use crate::device::{CL_DEVICE_TYPE_GPU, Device};
+    use crate::memory::{Buffer, CL_MEM_READ_WRITE};
+    use crate::platform::get_platforms;
+    use crate::types::{CL_BLOCKING, cl_float};
+    use std::ptr;
+
+    #[test]
+    fn test_percentile_and_json_string() {
+        let durations: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(Duration::from_millis(50), percentile(&durations, 50));
+        assert_eq!(Duration::from_millis(99), percentile(&durations, 99));
+        assert_eq!(Duration::from_millis(1), percentile(&durations[..1], 99));
+        assert_eq!(Duration::ZERO, percentile(&[], 50));
+
+        let mut json = String::new();
+        write_json_string(&mut json, "a \"b\"\\\n\u{1}");
+        assert_eq!("\"a \\\"b\\\"\\\\\\n\\u0001\"", json);
+    }
+
+    #[test]
+    fn test_profiler() {
+        let platforms = get_platforms().unwrap();
+        assert!(0 < platforms.len());
+
+        // Get the first platform
+        let platform = &platforms[0];
+
+        let devices = platform.get_devices(CL_DEVICE_TYPE_GPU).unwrap();
+        assert!(0 < devices.len());
+
+        // Get the first device
+        let device = Device::new(devices[0]);
+        let context = Context::from_device(&device).unwrap();
+
+        let mut queue = CommandQueueBuilder::new()
+            .set_profiling(true)
+            .build(&context)
+            .expect("CommandQueueBuilder::build failed");
+        let profiler = Profiler::new();
+        queue.set_profiler(Some(profiler.clone())).unwrap();
+
+        const ARRAY_SIZE: usize = 1024;
+        let ones: [cl_float; ARRAY_SIZE] = [1.0; ARRAY_SIZE];
+        let mut results: [cl_float; ARRAY_SIZE] = [0.0; ARRAY_SIZE];
+
+        let mut buffer = unsafe {
+            Buffer::<cl_float>::create(&context, CL_MEM_READ_WRITE, ARRAY_SIZE, ptr::null_mut())
+                .unwrap()
+        };
+
+        for _ in 0..3 {
+            let _ = unsafe {
+                queue
+                    .enqueue_write_buffer(&mut buffer, CL_BLOCKING, 0, &ones, &[])
+                    .unwrap()
+            };
+        }
+        let _ = unsafe {
+            queue
+                .enqueue_read_buffer(&buffer, CL_BLOCKING, 0, &mut results, &[])
+                .unwrap()
+        };
+        assert_eq!(4, profiler.len());
+
+        // Record an event from a command-queue without a Profiler
+        let other_queue = CommandQueueBuilder::new()
+            .set_profiling(true)
+            .build(&context)
+            .expect("CommandQueueBuilder::build failed");
+        let event = unsafe {
+            other_queue
+                .enqueue_read_buffer(&buffer, CL_BLOCKING, 0, &mut results, &[])
+                .unwrap()
+        };
+        profiler.record(&event, "read results").unwrap();
+        assert_eq!(5, profiler.len());
+
+        let timeline = profiler.timeline().unwrap();
+        assert_eq!(5, timeline.len());
+        assert_eq!(Some(ARRAY_SIZE * 4), timeline[0].bytes);
+        assert_eq!("CL_COMMAND_READ_BUFFER 4096 bytes", timeline[3].label);
+        assert_eq!(Some(ARRAY_SIZE * 4), timeline[3].bytes);
+        assert_eq!("read results", timeline[4].label);
+        assert_eq!(None, timeline[4].bytes);
+        assert_eq!(other_queue.get(), timeline[4].command_queue);
+
+        let summary = profiler.summary().unwrap();
+        for label in &summary {
+            println!("{}", label);
+        }
+        let writes = summary
+            .iter()
+            .find(|label| "CL_COMMAND_WRITE_BUFFER 4096 bytes" == label.label)
+            .unwrap();
+        assert_eq!(3, writes.count);
+        assert!(writes.p50 <= writes.p99);
+
+        let json = profiler.chrome_trace().unwrap();
+        println!("{}", json);
+        assert!(json.starts_with("{\"traceEvents\":[{\"name\":"));
+
+        // Detach the profiler
+        queue.set_profiler(None).unwrap();
+        let _ = unsafe {
+            queue
+                .enqueue_write_buffer(&mut buffer, CL_BLOCKING, 0, &ones, &[])
+                .unwrap()
+        };
+        assert_eq!(5, profiler.len());
+        profiler.clear();
+        assert!(profiler.is_empty());
+    }
+}
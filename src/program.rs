@@ -19,6 +19,7 @@ pub use cl3::program::*;
 use super::context::Context;
 
 use super::Result;
+use super::release::check_release;
 #[allow(unused_imports)]
 use cl3::error_codes::CL_BUILD_PROGRAM_FAILURE;
 #[allow(unused_imports)]
@@ -27,6 +28,7 @@ use cl3::ext;
 use libc::{c_void, intptr_t, size_t};
 #[allow(unused_imports)]
 use std::ffi::{CStr, CString};
+use std::mem::{self, ManuallyDrop};
 use std::ptr;
 use std::result;
 
@@ -95,7 +97,15 @@ impl From<Program> for cl_program {
 
 impl Drop for Program {
     fn drop(&mut self) {
-        unsafe { release_program(self.program).expect("Error: clReleaseProgram") };
+        check_release("clReleaseProgram", unsafe { release_program(self.program) });
+    }
+}
+
+impl Clone for Program {
+    /// Retain the OpenCL program, so that both Programs share it.
+    fn clone(&self) -> Self {
+        unsafe { retain_program(self.program).expect("Error: clRetainProgram") };
+        Self::new(self.program, &self.kernel_names)
     }
 }
 
@@ -115,6 +125,33 @@ impl Program {
         self.program
     }
 
+    /// Release the OpenCL program, returning any error from clReleaseProgram instead of
+    /// passing it to the [release error handler](crate::release) as Drop does.
+    pub fn close(self) -> Result<()> {
+        let mut this = ManuallyDrop::new(self);
+        drop(mem::take(&mut this.kernel_names));
+        Ok(unsafe { release_program(this.program) }?)
+    }
+
+    /// Create a Program from an OpenCL cl_program that is owned elsewhere.  
+    /// Calls clRetainProgram, so that the Program can release it when dropped.  
+    /// The kernel names are empty if the program has not been built.
+    ///
+    /// * `program` - a valid OpenCL cl_program.
+    ///
+    /// returns a Result containing the new Program
+    /// or the error code from the OpenCL C API function.
+    ///
+    /// # Safety
+    ///
+    /// `program` must be a valid OpenCL cl_program.
+    pub unsafe fn from_raw_retained(program: cl_program) -> Result<Self> {
+        unsafe { retain_program(program)? };
+        let mut program = Self::new(program, "");
+        program.kernel_names = program.get_kernel_names().unwrap_or_default();
+        Ok(program)
+    }
+
     /// Get the names of the OpenCL kernels in the Program, in a string
     /// separated by semicolons.
     #[allow(clippy::missing_const_for_fn)]
@@ -430,6 +467,11 @@ impl Program {
         Ok(intptr_t::from(get_program_info(self.program, CL_PROGRAM_CONTEXT)?) as cl_context)
     }
 
+    /// The Context of the program, retained so that it can outlive the Program.
+    pub fn retained_context(&self) -> Result<Context> {
+        unsafe { Context::from_raw_retained(self.get_context()?) }
+    }
+
     pub fn get_num_devices(&self) -> Result<cl_uint> {
         Ok(get_program_info(self.program, CL_PROGRAM_NUM_DEVICES)?.into())
     }
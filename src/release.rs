@@ -0,0 +1,121 @@
+// Copyright (c) 2026 Via Technology Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Handling of errors when OpenCL objects are released.
+//!
+//! The OpenCL object wrappers release their OpenCL objects when they are
+//! dropped. Since `Drop` cannot return an error, an error from an OpenCL
+//! release function, e.g. after a device reset, is passed to the release error
+//! handler instead of panicking.
+//! The handler also receives the other errors that occur in `Drop`, e.g. from
+//! `clWaitForEvents` when a pending transfer waits for its event, and errors
+//! from recording enqueued commands in a [Profiler](crate::profiler::Profiler),
+//! since the enqueue methods return the events of the enqueued commands.
+//! The default handler writes the error to stderr, an application can install
+//! its own handler with [set_release_error_handler], e.g. to log the error.
+//!
+//! Applications that need the error can call the `close` method of the
+//! wrapper instead of dropping it.
+
+use cl3::error_codes::ClError;
+use cl3::types::cl_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, RwLock};
+
+/// A handler for errors from OpenCL release functions, called with the name
+/// of the OpenCL function, e.g. "clReleaseContext", and the error.
+type ReleaseErrorHandler = Arc<dyn Fn(&str, ClError) + Send + Sync>;
+
+static RELEASE_ERROR_HANDLER: RwLock<Option<ReleaseErrorHandler>> = RwLock::new(None);
+
+/// Set the handler for errors from OpenCL release functions when OpenCL
+/// objects are dropped, replacing the default handler.
+///
+/// The handler may be called on any thread, including while a thread is
+/// panicking, so it should not panic; any panic in it is caught.
+///
+/// * `handler` - called with the name of the OpenCL function,
+///   e.g. "clReleaseContext", and the error.
+pub fn set_release_error_handler<F>(handler: F)
+where
+    F: Fn(&str, ClError) + Send + Sync + 'static,
+{
+    let mut value = RELEASE_ERROR_HANDLER
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    *value = Some(Arc::new(handler));
+}
+
+/// Restore the default handler for errors from OpenCL release functions,
+/// which writes the errors to stderr.
+pub fn reset_release_error_handler() {
+    let mut value = RELEASE_ERROR_HANDLER
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    *value = None;
+}
+
+/// Pass an error from an OpenCL release function to the release error handler.
+pub(crate) fn release_error(function: &str, error: ClError) {
+    let handler = RELEASE_ERROR_HANDLER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    // A panic in Drop while unwinding would abort the process
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| match handler {
+        Some(handler) => handler(function, error),
+        None => eprintln!("opencl3: {function} failed: {error}"),
+    }));
+}
+
+/// Handle the result of an OpenCL release function called from Drop.
+pub(crate) fn check_release(function: &str, result: Result<(), cl_int>) {
+    if let Err(code) = result {
+        release_error(function, ClError(code));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use cl3::error_codes::CL_INVALID_EVENT;
+    use std::ptr;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_release_error_handler() {
+        let errors = Arc::new(Mutex::new(Vec::<(String, ClError)>::new()));
+        let handler_errors = errors.clone();
+        set_release_error_handler(move |function, error| {
+            handler_errors
+                .lock()
+                .unwrap()
+                .push((function.to_owned(), error))
+        });
+
+        // Releasing an invalid event fails
+        drop(Event::new(ptr::null_mut()));
+        reset_release_error_handler();
+
+        let errors = errors.lock().unwrap();
+        println!("release errors: {:?}", errors);
+        assert_eq!(1, errors.len());
+        assert_eq!("clReleaseEvent", errors[0].0);
+        assert_eq!(CL_INVALID_EVENT, errors[0].1.0);
+
+        let result = Event::new(ptr::null_mut()).close();
+        assert_eq!(CL_INVALID_EVENT, result.unwrap_err().0);
+    }
+}
@@ -39,20 +39,20 @@ use std::ptr;
 #[allow(unused_imports)]
 use std::result;
 
-struct SvmRawVec<'a, T> {
+struct SvmRawVec<T> {
     ptr: *mut T,
     cap: usize,
-    context: &'a Context,
+    context: Context,
     fine_grain_buffer: bool,
     fine_grain_system: bool,
     atomics: bool,
 }
 
-unsafe impl<T: Send> Send for SvmRawVec<'_, T> {}
-unsafe impl<T: Sync> Sync for SvmRawVec<'_, T> {}
+unsafe impl<T: Send> Send for SvmRawVec<T> {}
+unsafe impl<T: Sync> Sync for SvmRawVec<T> {}
 
-impl<'a, T> SvmRawVec<'a, T> {
-    fn new(context: &'a Context, svm_capabilities: cl_device_svm_capabilities) -> Self {
+impl<T> SvmRawVec<T> {
+    fn new(context: &Context, svm_capabilities: cl_device_svm_capabilities) -> Self {
         assert!(0 < mem::size_of::<T>(), "No Zero Sized Types!");
 
         assert!(
@@ -65,10 +65,10 @@ impl<'a, T> SvmRawVec<'a, T> {
         let fine_grain_system: bool = svm_capabilities & CL_DEVICE_SVM_FINE_GRAIN_SYSTEM != 0;
         let atomics: bool = (fine_grain_buffer || fine_grain_system)
             && (svm_capabilities & CL_DEVICE_SVM_ATOMICS != 0);
-        SvmRawVec {
+        Self {
             ptr: ptr::null_mut(),
             cap: 0,
-            context,
+            context: context.clone(),
             fine_grain_buffer,
             fine_grain_system,
             atomics,
@@ -76,7 +76,7 @@ impl<'a, T> SvmRawVec<'a, T> {
     }
 
     fn with_capacity(
-        context: &'a Context,
+        context: &Context,
         svm_capabilities: cl_device_svm_capabilities,
         capacity: usize,
     ) -> Result<Self> {
@@ -87,7 +87,7 @@ impl<'a, T> SvmRawVec<'a, T> {
     }
 
     fn with_capacity_zeroed(
-        context: &'a Context,
+        context: &Context,
         svm_capabilities: cl_device_svm_capabilities,
         capacity: usize,
     ) -> Result<Self> {
@@ -168,7 +168,7 @@ impl<'a, T> SvmRawVec<'a, T> {
     }
 }
 
-impl<T> Drop for SvmRawVec<'_, T> {
+impl<T> Drop for SvmRawVec<T> {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
             if self.fine_grain_system {
@@ -187,7 +187,8 @@ impl<T> Drop for SvmRawVec<'_, T> {
 }
 
 /// An OpenCL Shared Virtual Memory (SVM) vector.
-/// It has the lifetime of the [Context] that it was constructed from.  
+/// It retains the [Context] that it was constructed from, so it does not
+/// borrow the [Context].  
 /// Note: T cannot be a "zero sized type" (ZST).
 ///
 /// There are three types of Shared Virtual Memory:
@@ -252,12 +253,12 @@ impl<T> Drop for SvmRawVec<'_, T> {
 /// # Ok(())
 /// # }
 /// ```
-pub struct SvmVec<'a, T> {
-    buf: SvmRawVec<'a, T>,
+pub struct SvmVec<T> {
+    buf: SvmRawVec<T>,
     len: usize,
 }
 
-impl<'a, T> SvmVec<'a, T> {
+impl<T> SvmVec<T> {
     #[must_use]
     const fn ptr(&self) -> *mut T {
         self.buf.ptr
@@ -324,7 +325,7 @@ impl<'a, T> SvmVec<'a, T> {
     }
 
     /// Construct an empty SvmVec from a [Context].  
-    /// The SvmVec has the lifetime of the [Context].
+    /// The SvmVec retains the [Context].
     ///
     /// # Panics
     ///
@@ -333,9 +334,9 @@ impl<'a, T> SvmVec<'a, T> {
     /// The cl_device_svm_capabilities must *not* include CL_DEVICE_SVM_FINE_GRAIN_SYSTEM,
     /// a standard Rust `Vec!` should be used instead.
     #[must_use]
-    pub fn new(context: &'a Context) -> Self {
+    pub fn new(context: &Context) -> Self {
         let svm_capabilities = context.get_svm_mem_capability();
-        SvmVec {
+        Self {
             buf: SvmRawVec::new(context, svm_capabilities),
             len: 0,
         }
@@ -352,9 +353,9 @@ impl<'a, T> SvmVec<'a, T> {
     /// CL_DEVICE_SVM_COARSE_GRAIN_BUFFER or CL_DEVICE_SVM_FINE_GRAIN_BUFFER.  
     /// The cl_device_svm_capabilities must *not* include CL_DEVICE_SVM_FINE_GRAIN_SYSTEM,
     /// a standard Rust `Vec!` should be used instead.
-    pub fn allocate(context: &'a Context, len: usize) -> Result<Self> {
+    pub fn allocate(context: &Context, len: usize) -> Result<Self> {
         let svm_capabilities = context.get_svm_mem_capability();
-        Ok(SvmVec {
+        Ok(Self {
             buf: SvmRawVec::with_capacity(context, svm_capabilities, len)?,
             len,
         })
@@ -370,9 +371,9 @@ impl<'a, T> SvmVec<'a, T> {
     /// CL_DEVICE_SVM_COARSE_GRAIN_BUFFER or CL_DEVICE_SVM_FINE_GRAIN_BUFFER.  
     /// The cl_device_svm_capabilities must *not* include CL_DEVICE_SVM_FINE_GRAIN_SYSTEM,
     /// a standard Rust `Vec!` should be used instead.
-    pub fn with_capacity(context: &'a Context, capacity: usize) -> Result<Self> {
+    pub fn with_capacity(context: &Context, capacity: usize) -> Result<Self> {
         let svm_capabilities = context.get_svm_mem_capability();
-        Ok(SvmVec {
+        Ok(Self {
             buf: SvmRawVec::with_capacity(context, svm_capabilities, capacity)?,
             len: 0,
         })
@@ -388,14 +389,14 @@ impl<'a, T> SvmVec<'a, T> {
     ///
     /// returns a Result containing an SvmVec with len values of zeroed
     /// memory, or the OpenCL error.
-    pub fn allocate_zeroed(context: &'a Context, len: usize) -> Result<Self> {
+    pub fn allocate_zeroed(context: &Context, len: usize) -> Result<Self> {
         let svm_capabilities = context.get_svm_mem_capability();
         let fine_grain_buffer: bool = svm_capabilities & CL_DEVICE_SVM_FINE_GRAIN_BUFFER != 0;
         assert!(
             fine_grain_buffer,
             "SVM is not fine grained, use `allocate` instead."
         );
-        Ok(SvmVec {
+        Ok(Self {
             buf: SvmRawVec::with_capacity_zeroed(context, svm_capabilities, len)?,
             len,
         })
@@ -508,9 +509,9 @@ impl<'a, T> SvmVec<'a, T> {
     }
 }
 
-impl<'a, T> IntoIterator for SvmVec<'a, T> {
+impl<T> IntoIterator for SvmVec<T> {
     type Item = T;
-    type IntoIter = IntoIter<'a, Self::Item>;
+    type IntoIter = IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
         unsafe {
@@ -523,27 +524,27 @@ impl<'a, T> IntoIterator for SvmVec<'a, T> {
     }
 }
 
-impl<T> Drop for SvmVec<'_, T> {
+impl<T> Drop for SvmVec<T> {
     fn drop(&mut self) {
         while self.pop().is_some() {}
         // allocation is handled by SvmRawVec
     }
 }
 
-impl<T> Deref for SvmVec<'_, T> {
+impl<T> Deref for SvmVec<T> {
     type Target = [T];
     fn deref(&self) -> &[T] {
         unsafe { std::slice::from_raw_parts(self.ptr(), self.len) }
     }
 }
 
-impl<T> DerefMut for SvmVec<'_, T> {
+impl<T> DerefMut for SvmVec<T> {
     fn deref_mut(&mut self) -> &mut [T] {
         unsafe { std::slice::from_raw_parts_mut(self.ptr(), self.len) }
     }
 }
 
-impl<T: Debug> fmt::Debug for SvmVec<'_, T> {
+impl<T: Debug> fmt::Debug for SvmVec<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
     }
@@ -551,12 +552,12 @@ impl<T: Debug> fmt::Debug for SvmVec<'_, T> {
 
 /// A DeserializeSeed implementation  that uses stateful deserialization to
 /// append array elements onto the end of an existing SvmVec.
-/// The pre-existing state ("seed") in this case is the SvmVec<'b, T>.
+/// The pre-existing state ("seed") in this case is the SvmVec<T>.
 #[cfg(feature = "serde")]
-pub struct ExtendSvmVec<'a, 'b, T: 'a>(pub &'a mut SvmVec<'b, T>);
+pub struct ExtendSvmVec<'a, T: 'a>(pub &'a mut SvmVec<T>);
 
 #[cfg(feature = "serde")]
-impl<'de, T> DeserializeSeed<'de> for ExtendSvmVec<'_, '_, T>
+impl<'de, T> DeserializeSeed<'de> for ExtendSvmVec<'_, T>
 where
     T: Deserialize<'de>,
 {
@@ -569,9 +570,9 @@ where
         D: Deserializer<'de>,
     {
         // Visitor implementation to walk an array of the deserializer input.
-        struct ExtendSvmVecVisitor<'a, 'b, T: 'a>(&'a mut SvmVec<'b, T>);
+        struct ExtendSvmVecVisitor<'a, T: 'a>(&'a mut SvmVec<T>);
 
-        impl<'de, T> Visitor<'de> for ExtendSvmVecVisitor<'_, '_, T>
+        impl<'de, T> Visitor<'de> for ExtendSvmVecVisitor<'_, T>
         where
             T: Deserialize<'de>,
         {
@@ -604,7 +605,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<T> Serialize for SvmVec<'_, T>
+impl<T> Serialize for SvmVec<T>
 where
     T: Serialize,
 {
@@ -688,12 +689,12 @@ impl<T> DoubleEndedIterator for RawValIter<T> {
     }
 }
 
-pub struct IntoIter<'a, T> {
-    _buf: SvmRawVec<'a, T>, // we don't actually care about this. Just need it to live.
+pub struct IntoIter<T> {
+    _buf: SvmRawVec<T>, // we don't actually care about this. Just need it to live.
     iter: RawValIter<T>,
 }
 
-impl<T> Iterator for IntoIter<'_, T> {
+impl<T> Iterator for IntoIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<T> {
         self.iter.next()
@@ -703,20 +704,20 @@ impl<T> Iterator for IntoIter<'_, T> {
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<'_, T> {
+impl<T> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<T> {
         self.iter.next_back()
     }
 }
 
-impl<T> Drop for IntoIter<'_, T> {
+impl<T> Drop for IntoIter<T> {
     fn drop(&mut self) {
         for _ in &mut *self {}
     }
 }
 
 pub struct Drain<'a, T: 'a> {
-    vec: PhantomData<&'a mut SvmVec<'a, T>>,
+    vec: PhantomData<&'a mut SvmVec<T>>,
     iter: RawValIter<T>,
 }
 